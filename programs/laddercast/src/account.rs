@@ -1,7 +1,21 @@
 use anchor_lang::prelude::*;
 
+use crate::utils::get_default_loot_tables;
+use crate::utils::ConsumableType;
+use crate::utils::EmissionSchedule;
+use crate::utils::ExperienceCurve;
 use crate::utils::ItemType;
+use crate::utils::LootTable;
 use crate::utils::Modifiers;
+use crate::utils::QueuedTurnPlan;
+use crate::utils::SecondaryStat;
+use crate::utils::SetBonuses;
+use crate::utils::SkillAllocation;
+use crate::utils::CHEST_TIERS;
+use crate::utils::DEFAULT_CANCEL_ACTION_REFUND_PERCENT;
+use crate::utils::DEFAULT_LADA_DISTRIBUTION_WEIGHTS;
+use crate::utils::MAX_INVENTORY_ITEMS;
+use crate::utils::MAX_QUEUED_TURNS;
 use crate::utils::TurnCommit;
 use crate::{GameTurnInfo, Tile};
 use core::mem::size_of;
@@ -33,6 +47,102 @@ impl Game {
         8 + 1 + 32 + (90 + 1) * Tile::SIZE + 8 + 1 + 32 + 32 + 32 + 32 + 32 + 32 + 300;
 }
 
+/// Settings that can change from one game (season) to another
+/// PDA with seeds ["game_config", game.key]
+#[account]
+pub struct GameConfig {
+    pub bump: u8,
+    pub game: Pubkey,
+    pub experience_curve: ExperienceCurve,
+    pub set_bonuses: SetBonuses,
+    /// Indexed by chest tier - 1, one table for each of the CHEST_TIERS tiers
+    pub loot_tables: [LootTable; 4],
+    /// Weight of each element in the LADA distribution, in the FIRE_INDEX, WATER_INDEX, EARTH_INDEX order
+    pub lada_distribution_weights: [u16; 3],
+    /// None gives LADA_DISTRIBUTION_PER_TURN every turn
+    pub emission_schedule: Option<EmissionSchedule>,
+    /// Share of the burned resources given back when an action is cancelled, 0-100 %
+    /// None uses DEFAULT_CANCEL_ACTION_REFUND_PERCENT
+    pub cancel_action_refund_percent: Option<u8>,
+    /// LADA all the chests opened during a turn can give, on top of the LADA of the casters
+    pub chest_lada_per_turn: u64,
+}
+impl GameConfig {
+    pub const SIZE: usize = 8
+        + 1
+        + 32
+        + ExperienceCurve::SIZE
+        + SetBonuses::SIZE
+        + LootTable::SIZE * CHEST_TIERS
+        + 2 * 3
+        + 1
+        + EmissionSchedule::SIZE
+        + 1
+        + 1
+        + 8
+        + 300;
+
+    /// Default settings, the ones of the games created before the config existed
+    pub fn new(game: Pubkey, bump: u8) -> Self {
+        Self {
+            bump,
+            game,
+            experience_curve: ExperienceCurve::default(),
+            set_bonuses: SetBonuses::default(),
+            loot_tables: get_default_loot_tables(),
+            lada_distribution_weights: DEFAULT_LADA_DISTRIBUTION_WEIGHTS,
+            emission_schedule: None,
+            cancel_action_refund_percent: None,
+            chest_lada_per_turn: 0,
+        }
+    }
+
+    /// The config is optional, games that don't have one yet play with the default settings
+    /// until the authority creates it with update_game_config. Instructions take it as an
+    /// UncheckedAccount with the ["game_config", game.key] seeds, so the PDA is always passed even
+    /// before it's created
+    pub fn load_or_default(game_config: &AccountInfo, game: Pubkey) -> Result<Self, ProgramError> {
+        if game_config.data_is_empty() {
+            return Ok(Self::new(game, 0));
+        }
+
+        let game_config: Account<GameConfig> = Account::try_from(game_config)?;
+
+        Ok((*game_config).clone())
+    }
+
+    /// Tier is 1 to 4, configs created before loot tables existed use the default tables
+    pub fn get_loot_table(&self, tier: u8) -> LootTable {
+        let tier = tier.clamp(1, CHEST_TIERS as u8);
+        let loot_table = self.loot_tables[tier as usize - 1];
+
+        if loot_table.is_empty() {
+            LootTable::get_default_for_tier(tier)
+        } else {
+            loot_table
+        }
+    }
+
+    /// Configs created before the weights existed weigh all elements the same
+    pub fn get_lada_distribution_weights(&self) -> [u16; 3] {
+        if self.lada_distribution_weights == [0; 3] {
+            DEFAULT_LADA_DISTRIBUTION_WEIGHTS
+        } else {
+            self.lada_distribution_weights
+        }
+    }
+
+    /// Configs created before the schedule existed give LADA_DISTRIBUTION_PER_TURN every turn
+    pub fn get_emission_schedule(&self) -> EmissionSchedule {
+        self.emission_schedule.unwrap_or_default()
+    }
+
+    pub fn get_cancel_action_refund_percent(&self) -> u8 {
+        self.cancel_action_refund_percent
+            .unwrap_or(DEFAULT_CANCEL_ACTION_REFUND_PERCENT)
+    }
+}
+
 /// Data about a specific turn
 /// PDA with seeds ["turn_data", game.key, turn as string]
 #[account]
@@ -43,10 +153,37 @@ pub struct TurnData {
     pub resource_3_burned: u64,
     //Backup of the map for that specific turn
     pub map: [[Option<Tile>; 3]; 30],
+    //LADA already given to the casters who redeemed this turn, what's left of the turn's LADA
+    //once everyone redeemed is the rounding dust
+    pub lada_distributed: u64,
+    //LADA of the turn from the emission schedule, set when the turn starts and limited to what's
+    //left in the game's LADA account
+    pub lada_to_distribute: u64,
+    //LADA given by the chests opened during this turn, limited by the chest LADA of the config
+    pub chest_lada_distributed: u64,
+    //Set with lada_to_distribute when the turn starts, turns started before the emission schedule
+    //existed don't have it
+    pub is_lada_set: bool,
 }
 
 impl TurnData {
-    pub const SIZE: usize = 8 + 1 + 8 + 8 + 8 + (90 + 1) * Tile::SIZE;
+    pub const SIZE: usize = 8 + 1 + 8 + 8 + 8 + (90 + 1) * Tile::SIZE + 8 + 8 + 8 + 1;
+
+    /// Turns started before the emission schedule existed don't have their LADA set, they give
+    /// the LADA of the schedule for that turn
+    pub fn get_lada_to_distribute(&self, emission_schedule: &EmissionSchedule, turn: u32) -> u64 {
+        if self.is_lada_set {
+            self.lada_to_distribute
+        } else {
+            emission_schedule.get_lada_for_turn(turn)
+        }
+    }
+
+    /// Once set, later changes of the emission schedule don't apply to the turn
+    pub fn set_lada_to_distribute(&mut self, lada_to_distribute: u64) {
+        self.lada_to_distribute = lada_to_distribute;
+        self.is_lada_set = true;
+    }
 }
 
 impl Default for TurnData {
@@ -57,6 +194,10 @@ impl Default for TurnData {
             resource_2_burned: 0,
             resource_3_burned: 0,
             map: [[None; 3]; 30],
+            lada_distributed: 0,
+            lada_to_distribute: 0,
+            chest_lada_distributed: 0,
+            is_lada_set: false,
         }
     }
 }
@@ -88,9 +229,16 @@ pub struct Player {
     pub authority: Pubkey,
     pub game: Pubkey,
     pub bump: u8,
+    /// Number of items created for the player, the next item PDA uses it as seed
+    pub item_counter: u64,
+    /// Sum of the budgets of the player's action queues, in the FIRE_INDEX, WATER_INDEX,
+    /// EARTH_INDEX order, the game signer is approved for this amount on the resource token accounts
+    pub queued_budget: [u64; 3],
+    /// Chests are opened by the player, not by a caster, so they are counted here
+    pub chests_opened: u64,
 }
 impl Player {
-    pub const SIZE: usize = 8 + 32 + 32 + 1 + 300;
+    pub const SIZE: usize = 8 + 32 + 32 + 1 + 8 + 8 * 3 + 8 + 300;
 }
 
 #[account]
@@ -104,10 +252,90 @@ pub struct Caster {
     pub modifiers: Modifiers,
     /// If filled cannot unequip/equip
     pub turn_commit: Option<TurnCommit>,
+    /// Number of times the caster went back to level 1 after reaching the max level
+    pub prestige: u8,
+    /// UTF-8 name padded with 0s, empty if the caster was never renamed
+    pub name: [u8; 32],
+    /// Earned on level up, not yet spent in the talent tree
+    pub skill_points: u8,
+    pub skills: SkillAllocation,
 }
 impl Caster {
-    pub const SIZE: usize =
-        8 + 1 + 1 + 32 + 32 + size_of::<Modifiers>() + 1 + size_of::<TurnCommit>() + 300;
+    pub const SIZE: usize = 8
+        + 1
+        + 1
+        + 32
+        + 32
+        + size_of::<Modifiers>()
+        + 1
+        + size_of::<TurnCommit>()
+        + 1
+        + 32
+        + 1
+        + SkillAllocation::SIZE
+        + 300;
+}
+
+/// Lifetime totals of a caster, used for leaderboards, achievements and profiles
+/// PDA with seeds ["caster_stats", caster.key]
+#[account]
+#[derive(Default)]
+pub struct CasterStats {
+    pub bump: u8,
+    pub caster: Pubkey,
+    /// Fire, water, earth
+    pub resources_looted: [u64; 3],
+    pub critical_hits: u64,
+    pub chests_found: u64,
+    pub spells_cast: u64,
+    pub spells_succeeded: u64,
+    pub items_crafted: u64,
+    pub lada_earned: u64,
+    pub turns_played: u64,
+}
+impl CasterStats {
+    pub const SIZE: usize = 8 + 1 + 32 + 8 * 3 + 8 * 7 + 300;
+}
+
+/// Consumables of one type owned by a player, stacked from consumable items
+/// PDA with seeds ["consumable_balance", player.key, consumable_type]
+#[account]
+pub struct ConsumableBalance {
+    pub bump: u8,
+    pub player: Pubkey,
+    pub consumable_type: ConsumableType,
+    pub amount: u64,
+}
+impl ConsumableBalance {
+    pub const SIZE: usize = 8 + 1 + 32 + 1 + 8 + 300;
+}
+
+/// Live items of a player, only kept up to date by the instructions it is passed to
+/// PDA with seeds ["inventory", player.key]
+#[account]
+pub struct Inventory {
+    pub bump: u8,
+    pub player: Pubkey,
+    pub items: Vec<Pubkey>,
+}
+impl Inventory {
+    pub const SIZE: usize = 8 + 1 + 32 + 4 + 32 * MAX_INVENTORY_ITEMS;
+}
+
+/// Turns planned in advance for a caster, executed in order by any keeper
+/// PDA with seeds ["action_queue", caster.key]
+#[account]
+pub struct ActionQueue {
+    pub bump: u8,
+    pub caster: Pubkey,
+    /// Next plan to execute is at index 0
+    pub plans: Vec<QueuedTurnPlan>,
+    /// Resources the keepers can still burn for this queue, in the FIRE_INDEX, WATER_INDEX,
+    /// EARTH_INDEX order
+    pub budget: [u64; 3],
+}
+impl ActionQueue {
+    pub const SIZE: usize = 8 + 1 + 32 + 4 + MAX_QUEUED_TURNS * QueuedTurnPlan::SIZE + 8 * 3;
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy)]
@@ -120,6 +348,10 @@ pub struct MetadataItem {
     pub item_type: ItemType,
     /// Caster
     pub equipped_owner: Option<Pubkey>,
+    pub reforge_count: u8,
+    pub wear: u16,
+    pub secondary_stat: Option<SecondaryStat>,
+    pub spell_book_charges: u8,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy)]
@@ -132,6 +364,10 @@ pub struct MetadataCaster {
     pub modifiers: Modifiers,
     /// If filled cannot unequip/equip
     pub turn_commit: Option<TurnCommit>,
+    pub prestige: u8,
+    pub name: [u8; 32],
+    pub skill_points: u8,
+    pub skills: SkillAllocation,
 }
 
 #[account]
@@ -145,7 +381,18 @@ pub struct Item {
     pub item_type: ItemType,
     /// Caster
     pub equipped_owner: Option<Pubkey>,
+    /// Number of times the value was re-rolled, each reforge costs more than the last
+    pub reforge_count: u8,
+    /// Durability lost, the item is broken once it reaches the max durability of its rarity
+    /// Stored as wear so items created before durability existed start as new
+    pub wear: u16,
+    /// Only on epic and legendary equipment
+    pub secondary_stat: Option<SecondaryStat>,
+    /// Casts left on a spell book, the spell book is destroyed after the last one
+    /// Spell books created before charges existed have 0, they keep their single cast
+    pub spell_book_charges: u8,
 }
 impl Item {
-    pub const SIZE: usize = 8 + 32 + 32 + 1 + size_of::<ItemType>() + 33 + 300;
+    pub const SIZE: usize =
+        8 + 32 + 32 + 1 + size_of::<ItemType>() + 33 + 1 + 2 + 1 + SecondaryStat::SIZE + 1 + 300;
 }
@@ -103,4 +103,148 @@ pub enum ErrorCode {
 
     #[msg("Item can't be equipped to mint it.")]
     ItemCantBeMintIfEquipped,
+
+    #[msg("Caster can't be transferred if it has equipped items.")]
+    InvalidCasterTransferEquipped,
+
+    #[msg("Caster can't be transferred if it has a pending turn.")]
+    InvalidCasterTransferPendingTurn,
+
+    #[msg("Caster can't be retired if it has equipped items.")]
+    InvalidCasterRetireEquipped,
+
+    #[msg("Caster can't be retired if it has a pending turn.")]
+    InvalidCasterRetirePendingTurn,
+
+    #[msg("Caster needs to be max level to prestige.")]
+    InvalidCasterPrestigeLevel,
+
+    #[msg("Caster can't prestige if it has equipped items.")]
+    InvalidCasterPrestigeEquipped,
+
+    #[msg("Caster can't prestige if it has a pending turn.")]
+    InvalidCasterPrestigePendingTurn,
+
+    #[msg("Invalid caster name.")]
+    InvalidCasterName,
+
+    #[msg("Actions can only be cancelled during the turn they were committed.")]
+    InvalidCancelTurn,
+
+    #[msg("This action wasn't committed this turn.")]
+    ActionNotCommitted,
+
+    #[msg("Another committed action depends on this one, cancel it first.")]
+    ActionHasDependentActions,
+
+    #[msg("A turn was already committed for this caster during the current turn.")]
+    TurnAlreadyCommitted,
+
+    #[msg("There is no planned turn left in the action queue.")]
+    ActionQueueEmpty,
+
+    #[msg("Too many turns planned in the action queue.")]
+    ActionQueueTooLong,
+
+    #[msg("An item of the planned crafting wasn't provided.")]
+    QueuedItemMissing,
+
+    #[msg("Invalid experience curve.")]
+    InvalidExperienceCurve,
+
+    #[msg("Not enough skill points.")]
+    NotEnoughSkillPoints,
+
+    #[msg("Skills can't be changed while you have a pending turn.")]
+    InvalidSkillChangePendingTurn,
+
+    #[msg("Item can't be salvaged if equipped.")]
+    ItemCantBeSalvagedIfEquipped,
+
+    #[msg("Item can't be salvaged.")]
+    ItemCantBeSalvaged,
+
+    #[msg("Item can't be reforged if equipped.")]
+    ItemCantBeReforgedIfEquipped,
+
+    #[msg("Only equipment can be reforged.")]
+    ItemCantBeReforged,
+
+    #[msg("Only damaged equipment can be repaired.")]
+    ItemCantBeRepaired,
+
+    #[msg("Caster can't be migrated.")]
+    InvalidCasterMigration,
+
+    #[msg("Item is not a consumable of this type.")]
+    ItemIsNotAConsumable,
+
+    #[msg("Not enough consumables.")]
+    NotEnoughConsumables,
+
+    #[msg("This consumable was already used this turn.")]
+    ConsumableAlreadyUsed,
+
+    #[msg("Invalid loot table.")]
+    InvalidLootTable,
+
+    #[msg("Not enough item accounts were provided to open the chest.")]
+    ChestItemAccountMissing,
+
+    #[msg("Not enough chest accounts were provided.")]
+    ChestAccountMissing,
+
+    #[msg("Chest doesn't belong to the player.")]
+    ChestNotOwned,
+
+    #[msg("Item account isn't the next item PDA of the player.")]
+    InvalidItemAccount,
+
+    #[msg("Inventory is full.")]
+    InventoryFull,
+
+    #[msg("Inventory doesn't belong to the player.")]
+    InventoryNotOwned,
+
+    #[msg("Item doesn't belong to the player.")]
+    ItemNotOwned,
+
+    #[msg("An item equipped by the caster wasn't provided.")]
+    LoadoutItemMissing,
+
+    #[msg("Math overflow.")]
+    MathOverflow,
+
+    #[msg("At least one element needs a LADA distribution weight.")]
+    InvalidLadaDistributionWeights,
+
+    #[msg("Invalid emission schedule.")]
+    InvalidEmissionSchedule,
+
+    #[msg("Caster doesn't belong to the player.")]
+    CasterNotOwned,
+
+    #[msg("Caster can't be transferred to the player who owns it.")]
+    InvalidCasterTransferSamePlayer,
+
+    #[msg("Invalid action.")]
+    InvalidActionOrderIndex,
+
+    #[msg("Crafting can't be cancelled, its items were already burned.")]
+    CraftingCantBeCancelled,
+
+    #[msg("Cancel action refund can't be more than 100 %.")]
+    InvalidCancelActionRefundPercent,
+
+    #[msg("Invalid planned turn in the action queue.")]
+    InvalidQueuedTurnPlan,
+
+    #[msg("Planned turn costs more than what's left of the action queue budget.")]
+    ActionQueueBudgetExceeded,
+
+    #[msg("Caster needs to be migrated to the current version first.")]
+    CasterNotMigrated,
+
+    #[msg("Too many chests opened in a single batch.")]
+    TooManyChestsInBatch,
 }
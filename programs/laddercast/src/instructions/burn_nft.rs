@@ -6,7 +6,7 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 
 use crate::account::{Caster, Game, Item, MetadataNFTCaster, MetadataNFTItem, Player};
 use crate::error::ErrorCode;
-use crate::utils::{Modifiers, GAME_CREATOR_AUTHORITY_PUBKEY};
+use crate::utils::{update_inventory, Modifiers, CASTER_VERSION, GAME_CREATOR_AUTHORITY_PUBKEY};
 
 #[derive(Accounts)]
 pub struct RedeemItem<'info> {
@@ -27,8 +27,15 @@ pub struct RedeemItem<'info> {
     bump = nft_metadata.self_bump,
     close = authority)]
     pub nft_metadata: Account<'info, MetadataNFTItem>,
-    #[account(init, payer = authority, space = Item::SIZE)]
+    #[account(init,
+    seeds = [b"item", player.key().as_ref(), player.item_counter.to_string().as_ref()],
+    bump,
+    payer = authority,
+    space = Item::SIZE)]
     pub item: Box<Account<'info, Item>>,
+
+    #[account(mut, seeds = [b"inventory", player.key().as_ref()], bump)]
+    pub inventory: UncheckedAccount<'info>,
 }
 
 pub fn redeem_item(ctx: Context<RedeemItem>) -> ProgramResult {
@@ -44,6 +51,20 @@ pub fn redeem_item(ctx: Context<RedeemItem>) -> ProgramResult {
     item.game = ctx.accounts.game.key();
     item.owner = ctx.accounts.player.key();
     item.equipped_owner = None;
+    item.reforge_count = item_metadata.reforge_count;
+    item.wear = item_metadata.wear;
+    item.secondary_stat = item_metadata.secondary_stat;
+    item.spell_book_charges = item_metadata.spell_book_charges;
+
+    ctx.accounts.player.item_counter = ctx.accounts.player.item_counter.checked_add(1).unwrap();
+
+    update_inventory(
+        &ctx.accounts.inventory,
+        &ctx.accounts.player.key(),
+        &[ctx.accounts.item.key()],
+        &[],
+        ctx.program_id,
+    )?;
 
     token::burn(
         CpiContext::new(
@@ -77,7 +98,8 @@ pub struct RedeemCaster<'info> {
     #[account(mut,
     seeds = [b"metadata".as_ref(), nft_mint.key().as_ref()],
     bump = nft_metadata.self_bump,
-    close = authority)]
+    close = authority,
+    constraint = nft_metadata.caster.version == CASTER_VERSION @ ErrorCode::CasterNotMigrated)]
     pub nft_metadata: Account<'info, MetadataNFTCaster>,
     #[account(init, payer = authority, space=Caster::SIZE)]
     pub caster: Box<Account<'info, Caster>>,
@@ -102,8 +124,14 @@ pub fn redeem_caster(ctx: Context<RedeemCaster>) -> ProgramResult {
         robe: None,
         staff: None,
         spell_book: None,
+        amulet: None,
+        ring: None,
     };
     caster.owner = ctx.accounts.player.key();
+    caster.prestige = caster_metadata.prestige;
+    caster.name = caster_metadata.name;
+    caster.skill_points = caster_metadata.skill_points;
+    caster.skills = caster_metadata.skills;
 
     token::burn(
         CpiContext::new(
@@ -0,0 +1,281 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::account::*;
+use crate::error::ErrorCode;
+use crate::utils::{
+    get_cancel_action_refund, get_current_tile, get_move_cost, set_caster_stats_owner, ActionType,
+    ItemFeature, ItemType, EARTH_INDEX, FIRE_INDEX, WATER_INDEX, CASTER_VERSION,
+};
+use crate::{Tile, TileType};
+
+#[derive(Accounts)]
+pub struct CancelAction<'info> {
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub game: Box<Account<'info, Game>>,
+    #[account(seeds = [b"game_config", game.key().as_ref()], bump)]
+    pub game_config: UncheckedAccount<'info>,
+    #[account(mut, has_one = authority, has_one = game)]
+    pub player: Box<Account<'info, Player>>,
+    #[account(
+    mut,
+    constraint = caster.owner == player.key(),
+    constraint = caster.version == CASTER_VERSION @ ErrorCode::CasterNotMigrated
+    )]
+    pub caster: Box<Account<'info, Caster>>,
+
+    #[account(init_if_needed,
+    seeds = [b"caster_stats", caster.key().as_ref()],
+    bump,
+    payer = authority,
+    space = CasterStats::SIZE)]
+    pub caster_stats: Box<Account<'info, CasterStats>>,
+
+    #[account(mut, seeds = [b"game_signer"], bump)]
+    pub game_signer: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = resource_1_mint_account.to_account_info().key() == game.resource_1_mint_account)]
+    pub resource_1_mint_account: Box<Account<'info, Mint>>,
+    #[account(mut, constraint = resource_2_mint_account.to_account_info().key() == game.resource_2_mint_account)]
+    pub resource_2_mint_account: Box<Account<'info, Mint>>,
+    #[account(mut, constraint = resource_3_mint_account.to_account_info().key() == game.resource_3_mint_account)]
+    pub resource_3_mint_account: Box<Account<'info, Mint>>,
+
+    #[account(init_if_needed,
+    associated_token::mint = resource_1_mint_account,
+    associated_token::authority = authority,
+    payer = authority)]
+    pub resource_1_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(init_if_needed,
+    associated_token::mint = resource_2_mint_account,
+    associated_token::authority = authority,
+    payer = authority)]
+    pub resource_2_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(init_if_needed,
+    associated_token::mint = resource_3_mint_account,
+    associated_token::authority = authority,
+    payer = authority)]
+    pub resource_3_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, seeds = [b"turn_data", game.to_account_info().key().as_ref(), game.turn_info.turn.to_string().as_ref()], bump = game_turn_data.bump)]
+    pub game_turn_data: Box<Account<'info, TurnData>>,
+    //When cancelling a spell, the equipped spell book needs to be passed as a remaining account
+    //at index 0 so that we know how much was burned
+}
+
+pub fn caster_cancel_action<'info>(
+    ctx: Context<'_, '_, '_, 'info, CancelAction<'info>>,
+    action: ActionType,
+) -> ProgramResult {
+    let game_config =
+        GameConfig::load_or_default(&ctx.accounts.game_config, ctx.accounts.game.key())?;
+    let map = ctx.accounts.game.map.clone();
+    let game_turn = ctx.accounts.game.turn_info.turn;
+    let caster = &mut ctx.accounts.caster;
+    let turn_data = &mut ctx.accounts.game_turn_data;
+
+    let mut caster_turn_commit = match caster.turn_commit {
+        None => {
+            return Err(ErrorCode::EmptyTurnCommit.into());
+        }
+        Some(turn_commit) => turn_commit,
+    };
+
+    //Once the turn is over the action has been locked in
+    if caster_turn_commit.turn != game_turn {
+        return Err(ErrorCode::InvalidCancelTurn.into());
+    }
+
+    let action_index = action.get_action_order_index();
+
+    if caster_turn_commit.actions.action_order[action_index as usize] == 0 {
+        return Err(ErrorCode::ActionNotCommitted.into());
+    }
+
+    let mut resources_to_refund: [u64; 3] = [0, 0, 0];
+
+    match action {
+        ActionType::Loot => {
+            caster_turn_commit.actions.loot = false;
+        }
+        ActionType::Spell => {
+            let spell_book_account = match ctx.remaining_accounts.get(0) {
+                None => {
+                    return Err(ErrorCode::SpellAccountMissing.into());
+                }
+                Some(spell_book_account) => spell_book_account,
+            };
+
+            if caster.modifiers.spell_book == None
+                || spell_book_account.key() != caster.modifiers.spell_book.unwrap()
+            {
+                return Err(ErrorCode::SpellKeyMismatch.into());
+            }
+
+            let spell_book: Account<Item> = Account::try_from(spell_book_account)?;
+
+            if let ItemType::SpellBook {
+                cost_feature, cost, ..
+            } = spell_book.item_type
+            {
+                match cost_feature {
+                    ItemFeature::Fire => resources_to_refund[FIRE_INDEX] = cost as u64,
+                    ItemFeature::Water => resources_to_refund[WATER_INDEX] = cost as u64,
+                    ItemFeature::Earth => resources_to_refund[EARTH_INDEX] = cost as u64,
+                    _ => {
+                        return Err(ErrorCode::InvalidSpellCost.into());
+                    }
+                }
+            }
+
+            caster_turn_commit.actions.spell = None;
+        }
+        ActionType::Move => {
+            //Loot and craft validate their tile against the move destination, so the move
+            //can't be cancelled once they were committed after it
+            if (caster_turn_commit.actions.loot
+                && caster_turn_commit
+                .actions
+                .is_committed_after(action_index, ActionType::Loot.get_action_order_index()))
+                || (caster_turn_commit.actions.crafting != None
+                && caster_turn_commit.actions.is_committed_after(
+                action_index,
+                ActionType::Crafting.get_action_order_index(),
+            ))
+            {
+                return Err(ErrorCode::ActionHasDependentActions.into());
+            }
+
+            let [dest_level, dest_column] = caster_turn_commit.actions.mv.unwrap();
+
+            //Map only changes on crank, so the tile is the same as when the move was committed
+            let potential_dest_tile: Option<&Tile> =
+                get_current_tile(&map, dest_level, dest_column);
+
+            if potential_dest_tile == None {
+                return Err(ErrorCode::TileNotExists.into());
+            }
+
+            //Nothing was burned for a move done with a move scroll
+            let resource_burned = if caster_turn_commit.actions.consumables.free_move {
+                0
+            } else {
+                get_move_cost(caster.modifiers.tile_level, dest_level)
+            };
+
+            match potential_dest_tile.unwrap().tile_type {
+                TileType::Fire => resources_to_refund[FIRE_INDEX] = resource_burned,
+                TileType::Water => resources_to_refund[WATER_INDEX] = resource_burned,
+                TileType::Earth => resources_to_refund[EARTH_INDEX] = resource_burned,
+                _ => {}
+            }
+
+            caster_turn_commit.actions.mv = None;
+        }
+        ActionType::Crafting => {
+            //The crafting items were burned when the crafting was committed
+            return Err(ErrorCode::CraftingCantBeCancelled.into());
+        }
+    }
+
+    caster_turn_commit.actions.remove_action_order(action_index)?;
+
+    //Cancelled burns don't count towards the LADA distribution anymore
+    for i in 0..resources_to_refund.len() {
+        caster_turn_commit.resources_burned[i] = caster_turn_commit.resources_burned[i]
+            .checked_sub(resources_to_refund[i])
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+
+    turn_data.resource_1_burned = turn_data
+        .resource_1_burned
+        .checked_sub(resources_to_refund[FIRE_INDEX])
+        .ok_or(ErrorCode::MathOverflow)?;
+    turn_data.resource_2_burned = turn_data
+        .resource_2_burned
+        .checked_sub(resources_to_refund[WATER_INDEX])
+        .ok_or(ErrorCode::MathOverflow)?;
+    turn_data.resource_3_burned = turn_data
+        .resource_3_burned
+        .checked_sub(resources_to_refund[EARTH_INDEX])
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    //Used consumables are kept for the rest of the turn
+    if caster_turn_commit.actions.is_empty()
+        && caster_turn_commit.resources_burned.iter().all(|burned| *burned == 0)
+        && caster_turn_commit.actions.consumables.is_empty()
+    {
+        caster.turn_commit = None;
+    } else {
+        caster.turn_commit = Some(caster_turn_commit);
+    }
+
+    let caster_stats = &mut ctx.accounts.caster_stats;
+    set_caster_stats_owner(
+        caster_stats,
+        caster.key(),
+        *ctx.bumps.get("caster_stats").unwrap(),
+    );
+
+    //Cancelled actions don't count in the stats
+    if action == ActionType::Spell {
+        caster_stats.spells_cast = caster_stats.spells_cast.saturating_sub(1);
+    }
+
+    if caster.turn_commit == None {
+        caster_stats.turns_played = caster_stats.turns_played.saturating_sub(1);
+    }
+
+    let refund_percent = game_config.get_cancel_action_refund_percent();
+
+    let seeds = &[b"game_signer".as_ref(), &[ctx.accounts.game.signer_bump]];
+    let signer = &[&seeds[..]];
+
+    for (index, (mint, token_account)) in [
+        (
+            &ctx.accounts.resource_1_mint_account,
+            &ctx.accounts.resource_1_token_account,
+        ),
+        (
+            &ctx.accounts.resource_2_mint_account,
+            &ctx.accounts.resource_2_token_account,
+        ),
+        (
+            &ctx.accounts.resource_3_mint_account,
+            &ctx.accounts.resource_3_token_account,
+        ),
+    ]
+        .iter()
+        .enumerate()
+    {
+        let refund = get_cancel_action_refund(resources_to_refund[index], refund_percent);
+
+        if refund == 0 {
+            continue;
+        }
+
+        token::mint_to(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info().clone(),
+                token::MintTo {
+                    mint: mint.to_account_info(),
+                    to: token_account.to_account_info(),
+                    authority: ctx.accounts.game_signer.to_account_info(),
+                },
+            )
+                .with_signer(signer),
+            refund,
+        )?;
+    }
+
+    Ok(())
+}
@@ -1,12 +1,11 @@
 use anchor_lang::{prelude::*, solana_program::sysvar};
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
 use crate::{Tile, TileType};
 use crate::account::*;
 use crate::error::ErrorCode;
-use crate::utils::{GAME_CREATOR_AUTHORITY_PUBKEY, get_current_tile, ItemRarity, ItemType, zombify_account};
+use crate::utils::{ActionType, burn_resources, set_caster_stats_owner, GAME_CREATOR_AUTHORITY_PUBKEY, get_crafting_cost, get_current_tile, ItemRarity, ItemType, update_inventory, CASTER_VERSION};
 use crate::utils::CraftingSnapshot;
 use crate::utils::TurnCommit;
 
@@ -24,9 +23,20 @@ pub struct Craft<'info> {
     pub game: Box<Account<'info, Game>>,
     #[account(mut, has_one = authority, has_one = game)]
     pub player: Box<Account<'info, Player>>,
-    #[account(mut, constraint = caster.owner == player.key())]
+    #[account(
+    mut,
+    constraint = caster.owner == player.key(),
+    constraint = caster.version == CASTER_VERSION @ ErrorCode::CasterNotMigrated
+    )]
     pub caster: Box<Account<'info, Caster>>,
 
+    #[account(init_if_needed,
+    seeds = [b"caster_stats", caster.key().as_ref()],
+    bump,
+    payer = authority,
+    space = CasterStats::SIZE)]
+    pub caster_stats: Box<Account<'info, CasterStats>>,
+
     #[account(address = sysvar::slot_hashes::id())]
     pub slots: UncheckedAccount<'info>,
 
@@ -62,15 +72,82 @@ pub struct Craft<'info> {
 
     #[account(mut, seeds = [b"turn_data", game.to_account_info().key().as_ref(), game.turn_info.turn.to_string().as_ref()], bump = game_turn_data.bump)]
     pub game_turn_data: Box<Account<'info, TurnData>>,
+
+    #[account(mut, seeds = [b"inventory", player.key().as_ref()], bump)]
+    pub inventory: UncheckedAccount<'info>,
 }
 
 pub fn caster_commit_craft(ctx: Context<Craft>) -> ProgramResult {
     let map = ctx.accounts.game.map.clone();
     let game_turn = ctx.accounts.game.turn_info.turn;
+    let is_new_turn = ctx.accounts.caster.turn_commit == None;
+
+    let resources_to_burn = commit_craft(
+        &mut ctx.accounts.caster,
+        &mut ctx.accounts.game_turn_data,
+        &map,
+        game_turn,
+        [&ctx.accounts.item_1, &ctx.accounts.item_2, &ctx.accounts.item_3],
+        [
+            ctx.accounts.resource_1_token_account.amount,
+            ctx.accounts.resource_2_token_account.amount,
+            ctx.accounts.resource_3_token_account.amount,
+        ],
+    )?;
+
+    let caster_stats = &mut ctx.accounts.caster_stats;
+    set_caster_stats_owner(
+        caster_stats,
+        ctx.accounts.caster.key(),
+        *ctx.bumps.get("caster_stats").unwrap(),
+    );
+
+    if is_new_turn {
+        caster_stats.turns_played = caster_stats.turns_played.saturating_add(1);
+    }
+
+    burn_resources(
+        ctx.accounts.token_program.to_account_info(),
+        [
+            ctx.accounts.resource_1_mint_account.to_account_info(),
+            ctx.accounts.resource_2_mint_account.to_account_info(),
+            ctx.accounts.resource_3_mint_account.to_account_info(),
+        ],
+        [
+            ctx.accounts.resource_1_token_account.to_account_info(),
+            ctx.accounts.resource_2_token_account.to_account_info(),
+            ctx.accounts.resource_3_token_account.to_account_info(),
+        ],
+        ctx.accounts.authority.to_account_info(),
+        &[],
+        resources_to_burn,
+    )?;
 
-    let caster = &mut ctx.accounts.caster;
-    let turn_data = &mut ctx.accounts.game_turn_data;
+    update_inventory(
+        &ctx.accounts.inventory,
+        &ctx.accounts.player.key(),
+        &[],
+        &[
+            ctx.accounts.item_1.key(),
+            ctx.accounts.item_2.key(),
+            ctx.accounts.item_3.key(),
+        ],
+        ctx.program_id,
+    )?;
+
+    Ok(())
+}
 
+//Shared with the action queue, validates and adds the crafting to the caster's turn commit and
+//returns the resources that need to be burned for it, burning the items is left to the caller
+pub fn commit_craft(
+    caster: &mut Caster,
+    turn_data: &mut TurnData,
+    map: &[[Option<Tile>; 3]; 30],
+    game_turn: u32,
+    items: [&Item; 3],
+    resource_balances: [u64; 3],
+) -> Result<[u64; 3], ProgramError> {
     let mut caster_turn_commit: TurnCommit = match caster.turn_commit.clone() {
         Some(turn_commit) => turn_commit,
         None => TurnCommit {
@@ -94,18 +171,12 @@ pub fn caster_commit_craft(ctx: Context<Craft>) -> ProgramResult {
     };
 
     //Need to do this because if user moved to a crafting tile during that turn, then crafting is authorized
-    let (dest_level, dest_column): (u8, u8) = {
-        if caster_turn_commit.actions.mv != None {
-            (
-                caster_turn_commit.actions.mv.unwrap()[0],
-                caster_turn_commit.actions.mv.unwrap()[1],
-            )
-        } else {
-            (caster.modifiers.tile_level, caster.modifiers.tile_column)
-        }
-    };
+    let (dest_level, dest_column): (u8, u8) = caster_turn_commit.actions.get_action_tile(
+        ActionType::Crafting.get_action_order_index(),
+        (caster.modifiers.tile_level, caster.modifiers.tile_column),
+    );
 
-    let potential_current_tile: Option<&Tile> = get_current_tile(&map, dest_level, dest_column);
+    let potential_current_tile: Option<&Tile> = get_current_tile(map, dest_level, dest_column);
 
     if potential_current_tile == None {
         return Err(ErrorCode::TileNotExists.into());
@@ -117,25 +188,12 @@ pub fn caster_commit_craft(ctx: Context<Craft>) -> ProgramResult {
         return Err(ErrorCode::NotCraftingTile.into());
     }
 
-    //Take the resources cost for crafting (since 0 based, gotta add 1)
-    let per_resource_burn = caster.modifiers.tile_level.checked_add(1).unwrap().checked_mul(5).unwrap() as u64;
-
-    let resource_1_token_account = &ctx.accounts.resource_1_token_account;
-    let resource_2_token_account = &ctx.accounts.resource_2_token_account;
-    let resource_3_token_account = &ctx.accounts.resource_3_token_account;
-
-    if resource_1_token_account
-        .amount
-        .checked_sub(per_resource_burn)
-        == None
-        || resource_2_token_account
-        .amount
-        .checked_sub(per_resource_burn)
-        == None
-        || resource_3_token_account
-        .amount
-        .checked_sub(per_resource_burn)
-        == None
+    //Take the resources cost for crafting, based on the caster's level on the ladder
+    let per_resource_burn = get_crafting_cost(caster.modifiers.tile_level);
+
+    if resource_balances
+        .iter()
+        .any(|balance| balance.checked_sub(per_resource_burn) == None)
     {
         return Err(ErrorCode::PlayerIsPoor.into());
     }
@@ -144,52 +202,6 @@ pub fn caster_commit_craft(ctx: Context<Craft>) -> ProgramResult {
     turn_data.resource_2_burned += per_resource_burn;
     turn_data.resource_3_burned += per_resource_burn;
 
-    token::burn(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info().clone(),
-            token::Burn {
-                mint: ctx
-                    .accounts
-                    .resource_1_mint_account
-                    .to_account_info()
-                    .clone(),
-                to: resource_1_token_account.to_account_info().clone(), // STUPID NAME: its the token account you are burning the tokens from
-                authority: ctx.accounts.authority.to_account_info(),
-            },
-        ),
-        per_resource_burn,
-    )?;
-    token::burn(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info().clone(),
-            token::Burn {
-                mint: ctx
-                    .accounts
-                    .resource_2_mint_account
-                    .to_account_info()
-                    .clone(),
-                to: resource_2_token_account.to_account_info().clone(), // STUPID NAME: its the token account you are burning the tokens from
-                authority: ctx.accounts.authority.to_account_info(),
-            },
-        ),
-        per_resource_burn,
-    )?;
-    token::burn(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info().clone(),
-            token::Burn {
-                mint: ctx
-                    .accounts
-                    .resource_3_mint_account
-                    .to_account_info()
-                    .clone(),
-                to: resource_3_token_account.to_account_info().clone(), // STUPID NAME: its the token account you are burning the tokens from
-                authority: ctx.accounts.authority.to_account_info(),
-            },
-        ),
-        per_resource_burn,
-    )?;
-
     //If not a legendary crafting tile, can only go up to epic, if it is can go up to legendary
     if current_tile.tile_type == TileType::Legendary {
         crafting_snapshot.max_rarity = ItemRarity::Legendary;
@@ -197,13 +209,7 @@ pub fn caster_commit_craft(ctx: Context<Craft>) -> ProgramResult {
         crafting_snapshot.max_rarity = ItemRarity::Epic;
     }
 
-    for removed_item in [
-        ctx.accounts.item_1.clone(),
-        ctx.accounts.item_2.clone(),
-        ctx.accounts.item_3.clone(),
-    ]
-        .iter()
-    {
+    for removed_item in items.iter() {
         if removed_item.level < crafting_snapshot.min_level {
             crafting_snapshot.min_level = removed_item.level;
         }
@@ -249,24 +255,7 @@ pub fn caster_commit_craft(ctx: Context<Craft>) -> ProgramResult {
 
     caster_turn_commit.actions.add_new_action_order(3);
 
-    ctx.accounts.caster.turn_commit = Some(caster_turn_commit);
+    caster.turn_commit = Some(caster_turn_commit);
 
-    //Zombifies the 3 item accounts
-    zombify_account(
-        &mut ctx.accounts.item_1,
-        ctx.accounts.authority.to_account_info(),
-        ctx.program_id,
-    )?;
-    zombify_account(
-        &mut ctx.accounts.item_2,
-        ctx.accounts.authority.to_account_info(),
-        ctx.program_id,
-    )?;
-    zombify_account(
-        &mut ctx.accounts.item_3,
-        ctx.accounts.authority.to_account_info(),
-        ctx.program_id,
-    )?;
-
-    Ok(())
+    Ok([per_resource_burn, per_resource_burn, per_resource_burn])
 }
@@ -3,7 +3,7 @@ use anchor_lang::prelude::*;
 use crate::{Tile, TileType};
 use crate::account::*;
 use crate::error::ErrorCode;
-use crate::utils::{GAME_CREATOR_AUTHORITY_PUBKEY, get_current_tile, TurnCommit};
+use crate::utils::{ActionType, GAME_CREATOR_AUTHORITY_PUBKEY, get_current_tile, set_caster_stats_owner, TurnCommit, CASTER_VERSION};
 
 #[derive(Accounts)]
 pub struct Loot<'info> {
@@ -16,18 +16,50 @@ pub struct Loot<'info> {
     pub game: Box<Account<'info, Game>>,
     #[account(mut, has_one = authority, has_one = game)]
     pub player: Account<'info, Player>,
-    #[account(mut, constraint = caster.owner == player.key())]
+    #[account(
+    mut,
+    constraint = caster.owner == player.key(),
+    constraint = caster.version == CASTER_VERSION @ ErrorCode::CasterNotMigrated
+    )]
     pub caster: Account<'info, Caster>,
 
+    #[account(init_if_needed,
+    seeds = [b"caster_stats", caster.key().as_ref()],
+    bump,
+    payer = authority,
+    space = CasterStats::SIZE)]
+    pub caster_stats: Box<Account<'info, CasterStats>>,
+
     #[account(mut, seeds = [b"turn_data", game.to_account_info().key().as_ref(), game.turn_info.turn.to_string().as_ref()], bump = game_turn_data.bump)]
     pub game_turn_data: Account<'info, TurnData>,
 }
 
 pub fn caster_commit_loot(ctx: Context<Loot>) -> ProgramResult {
     let game_turn = ctx.accounts.game.turn_info.turn;
+    let is_new_turn = ctx.accounts.caster.turn_commit == None;
+
+    commit_loot(&mut ctx.accounts.caster, &ctx.accounts.game.map, game_turn)?;
+
+    let caster_stats = &mut ctx.accounts.caster_stats;
+    set_caster_stats_owner(
+        caster_stats,
+        ctx.accounts.caster.key(),
+        *ctx.bumps.get("caster_stats").unwrap(),
+    );
+
+    if is_new_turn {
+        caster_stats.turns_played = caster_stats.turns_played.saturating_add(1);
+    }
 
-    let caster = &mut ctx.accounts.caster;
+    Ok(())
+}
 
+//Shared with the action queue, validates and adds the loot action to the caster's turn commit
+pub fn commit_loot(
+    caster: &mut Caster,
+    map: &[[Option<Tile>; 3]; 30],
+    game_turn: u32,
+) -> ProgramResult {
     let mut caster_turn_commit: TurnCommit = match caster.turn_commit.clone() {
         Some(turn_commit) => turn_commit,
         None => TurnCommit {
@@ -41,23 +73,13 @@ pub fn caster_commit_loot(ctx: Context<Loot>) -> ProgramResult {
     }
 
     //Need to do this because if user moved to a looting tile during that turn, then looting is authorized
-    let (dest_level, dest_column): (u8, u8) = {
-        if caster_turn_commit.actions.mv != None {
-            (
-                caster_turn_commit.actions.mv.unwrap()[0],
-                caster_turn_commit.actions.mv.unwrap()[1],
-            )
-        } else {
-            (caster.modifiers.tile_level, caster.modifiers.tile_column)
-        }
-    };
-
-    let potential_looted_tile: Option<&Tile> = get_current_tile(
-        &ctx.accounts.game.map,
-        dest_level,
-        dest_column,
+    let (dest_level, dest_column): (u8, u8) = caster_turn_commit.actions.get_action_tile(
+        ActionType::Loot.get_action_order_index(),
+        (caster.modifiers.tile_level, caster.modifiers.tile_column),
     );
 
+    let potential_looted_tile: Option<&Tile> = get_current_tile(map, dest_level, dest_column);
+
     if potential_looted_tile == None {
         return Err(ErrorCode::TileNotExists.into());
     }
@@ -80,7 +102,7 @@ pub fn caster_commit_loot(ctx: Context<Loot>) -> ProgramResult {
 
     caster_turn_commit.actions.add_new_action_order(0);
 
-    ctx.accounts.caster.turn_commit = Some(caster_turn_commit);
+    caster.turn_commit = Some(caster_turn_commit);
 
     Ok(())
 }
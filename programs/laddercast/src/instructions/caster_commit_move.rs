@@ -1,12 +1,11 @@
 use anchor_lang::{prelude::*, solana_program::sysvar};
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
 use crate::{Tile, TileType};
 use crate::account::*;
 use crate::error::ErrorCode;
-use crate::utils::{EARTH_INDEX, FIRE_INDEX, GAME_CREATOR_AUTHORITY_PUBKEY, get_current_tile, WATER_INDEX};
+use crate::utils::{burn_resources, set_caster_stats_owner, EARTH_INDEX, FIRE_INDEX, GAME_CREATOR_AUTHORITY_PUBKEY, get_current_tile, get_move_cost, is_valid_move, WATER_INDEX, CASTER_VERSION};
 use crate::utils::TurnCommit;
 
 #[derive(Accounts)]
@@ -23,9 +22,20 @@ pub struct Move<'info> {
     pub game: Box<Account<'info, Game>>,
     #[account(mut, has_one = authority, has_one = game)]
     pub player: Box<Account<'info, Player>>,
-    #[account(mut, constraint = caster.owner == player.key())]
+    #[account(
+    mut,
+    constraint = caster.owner == player.key(),
+    constraint = caster.version == CASTER_VERSION @ ErrorCode::CasterNotMigrated
+    )]
     pub caster: Box<Account<'info, Caster>>,
 
+    #[account(init_if_needed,
+    seeds = [b"caster_stats", caster.key().as_ref()],
+    bump,
+    payer = authority,
+    space = CasterStats::SIZE)]
+    pub caster_stats: Box<Account<'info, CasterStats>>,
+
     #[account(address = sysvar::slot_hashes::id())]
     pub slots: UncheckedAccount<'info>,
 
@@ -59,9 +69,62 @@ pub struct Move<'info> {
 pub fn caster_commit_move(ctx: Context<Move>, lvl: u8, clm: u8) -> ProgramResult {
     let map = ctx.accounts.game.map.clone();
     let game_turn = ctx.accounts.game.turn_info.turn;
-    let caster = &mut ctx.accounts.caster;
-    let turn_data = &mut ctx.accounts.game_turn_data;
+    let is_new_turn = ctx.accounts.caster.turn_commit == None;
+
+    let resources_to_burn = commit_move(
+        &mut ctx.accounts.caster,
+        &mut ctx.accounts.game_turn_data,
+        &map,
+        game_turn,
+        lvl,
+        clm,
+        [
+            ctx.accounts.resource_1_token_account.amount,
+            ctx.accounts.resource_2_token_account.amount,
+            ctx.accounts.resource_3_token_account.amount,
+        ],
+    )?;
+
+    let caster_stats = &mut ctx.accounts.caster_stats;
+    set_caster_stats_owner(
+        caster_stats,
+        ctx.accounts.caster.key(),
+        *ctx.bumps.get("caster_stats").unwrap(),
+    );
+
+    if is_new_turn {
+        caster_stats.turns_played = caster_stats.turns_played.saturating_add(1);
+    }
+
+    burn_resources(
+        ctx.accounts.token_program.to_account_info(),
+        [
+            ctx.accounts.resource_1_mint_account.to_account_info(),
+            ctx.accounts.resource_2_mint_account.to_account_info(),
+            ctx.accounts.resource_3_mint_account.to_account_info(),
+        ],
+        [
+            ctx.accounts.resource_1_token_account.to_account_info(),
+            ctx.accounts.resource_2_token_account.to_account_info(),
+            ctx.accounts.resource_3_token_account.to_account_info(),
+        ],
+        ctx.accounts.authority.to_account_info(),
+        &[],
+        resources_to_burn,
+    )
+}
 
+//Shared with the action queue, validates and adds the move to the caster's turn commit and
+//returns the resources that need to be burned for it
+pub fn commit_move(
+    caster: &mut Caster,
+    turn_data: &mut TurnData,
+    map: &[[Option<Tile>; 3]; 30],
+    game_turn: u32,
+    lvl: u8,
+    clm: u8,
+    resource_balances: [u64; 3],
+) -> Result<[u64; 3], ProgramError> {
     let mut caster_turn_commit: TurnCommit = match caster.turn_commit.clone() {
         Some(turn_commit) => turn_commit,
         None => TurnCommit {
@@ -80,21 +143,17 @@ pub fn caster_commit_move(ctx: Context<Move>, lvl: u8, clm: u8) -> ProgramResult
 
     let (dest_level, dest_column): (u8, u8) = (lvl, clm);
 
-    //Can only go up the ladder, and can only go if your caster is the right level
-    if dest_level < caster.modifiers.tile_level || dest_level > caster.level {
+    if !is_valid_move(
+        caster.modifiers.tile_level,
+        caster.modifiers.tile_column,
+        dest_level,
+        dest_column,
+        caster.level,
+    ) {
         return Err(ErrorCode::InvalidMove.into());
     }
 
-    let d_col = dest_column as i8;
-    let f_col = caster.modifiers.tile_column as i8;
-    let distance = (d_col - f_col).abs();
-
-    //Can only move to the column next to you or move up by 1
-    if distance > 1 || (dest_level > caster.modifiers.tile_level && distance != 0) {
-        return Err(ErrorCode::InvalidMove.into());
-    }
-
-    let potential_dest_tile: Option<&Tile> = get_current_tile(&map, dest_level, dest_column);
+    let potential_dest_tile: Option<&Tile> = get_current_tile(map, dest_level, dest_column);
 
     if potential_dest_tile == None {
         return Err(ErrorCode::TileNotExists.into());
@@ -102,111 +161,42 @@ pub fn caster_commit_move(ctx: Context<Move>, lvl: u8, clm: u8) -> ProgramResult
 
     let dest_tile = potential_dest_tile.unwrap();
 
-    //MOVE costs 10*resource of tile you're moving too
-    let resource_burned = (dest_level + 1).checked_mul(10).unwrap() as u64;
+    //MOVE costs 10*resource of tile you're moving too, more when going down, unless a move
+    //scroll was used this turn
+    let resource_burned = if caster_turn_commit.actions.consumables.free_move {
+        0
+    } else {
+        get_move_cost(caster.modifiers.tile_level, dest_level)
+    };
+
+    let mut resources_to_burn: [u64; 3] = [0, 0, 0];
 
     match dest_tile.tile_type {
-        TileType::Fire => {
-            if ctx.accounts.resource_1_token_account.amount.checked_sub(resource_burned) == None {
-                return Err(ErrorCode::PlayerIsPoor.into());
-            }
-
-            turn_data.resource_1_burned += resource_burned;
-
-            caster_turn_commit.resources_burned[FIRE_INDEX] = caster_turn_commit.resources_burned[FIRE_INDEX]
-                .checked_add(resource_burned)
-                .unwrap();
-
-            token::burn(
-                CpiContext::new(
-                    ctx.accounts.token_program.to_account_info().clone(),
-                    token::Burn {
-                        mint: ctx
-                            .accounts
-                            .resource_1_mint_account
-                            .to_account_info()
-                            .clone(),
-                        to: ctx
-                            .accounts
-                            .resource_1_token_account
-                            .to_account_info()
-                            .clone(), // STUPID NAME: its the token account you are burning the tokens from
-                        authority: ctx.accounts.authority.to_account_info(),
-                    },
-                ),
-                resource_burned,
-            )?;
-        }
-        TileType::Water => {
-            if ctx.accounts.resource_2_token_account.amount.checked_sub(resource_burned) == None {
-                return Err(ErrorCode::PlayerIsPoor.into());
-            }
-
-            turn_data.resource_2_burned += resource_burned;
-
-            caster_turn_commit.resources_burned[WATER_INDEX] = caster_turn_commit.resources_burned[WATER_INDEX]
-                .checked_add(resource_burned)
-                .unwrap();
-
-            token::burn(
-                CpiContext::new(
-                    ctx.accounts.token_program.to_account_info().clone(),
-                    token::Burn {
-                        mint: ctx
-                            .accounts
-                            .resource_2_mint_account
-                            .to_account_info()
-                            .clone(),
-                        to: ctx
-                            .accounts
-                            .resource_2_token_account
-                            .to_account_info()
-                            .clone(), // STUPID NAME: its the token account you are burning the tokens from
-                        authority: ctx.accounts.authority.to_account_info(),
-                    },
-                ),
-                resource_burned,
-            )?;
-        }
-        TileType::Earth => {
-            if ctx.accounts.resource_3_token_account.amount.checked_sub(resource_burned) == None {
-                return Err(ErrorCode::PlayerIsPoor.into());
-            }
-
-            turn_data.resource_3_burned += resource_burned;
-
-            caster_turn_commit.resources_burned[EARTH_INDEX] = caster_turn_commit.resources_burned[EARTH_INDEX]
-                .checked_add(resource_burned)
-                .unwrap();
-
-            token::burn(
-                CpiContext::new(
-                    ctx.accounts.token_program.to_account_info().clone(),
-                    token::Burn {
-                        mint: ctx
-                            .accounts
-                            .resource_3_mint_account
-                            .to_account_info()
-                            .clone(),
-                        to: ctx
-                            .accounts
-                            .resource_3_token_account
-                            .to_account_info()
-                            .clone(), // STUPID NAME: its the token account you are burning the tokens from
-                        authority: ctx.accounts.authority.to_account_info(),
-                    },
-                ),
-                resource_burned,
-            )?;
-        }
+        TileType::Fire => resources_to_burn[FIRE_INDEX] = resource_burned,
+        TileType::Water => resources_to_burn[WATER_INDEX] = resource_burned,
+        TileType::Earth => resources_to_burn[EARTH_INDEX] = resource_burned,
         _ => {}
     }
 
+    for i in 0..resources_to_burn.len() {
+        if resource_balances[i].checked_sub(resources_to_burn[i]) == None {
+            return Err(ErrorCode::PlayerIsPoor.into());
+        }
+
+        caster_turn_commit.resources_burned[i] = caster_turn_commit.resources_burned[i]
+            .checked_add(resources_to_burn[i])
+            .unwrap();
+    }
+
+    turn_data.resource_1_burned += resources_to_burn[FIRE_INDEX];
+    turn_data.resource_2_burned += resources_to_burn[WATER_INDEX];
+    turn_data.resource_3_burned += resources_to_burn[EARTH_INDEX];
+
     caster_turn_commit.actions.mv = Some([dest_level, dest_column]);
 
     caster_turn_commit.actions.add_new_action_order(2);
 
-    ctx.accounts.caster.turn_commit = Some(caster_turn_commit);
+    caster.turn_commit = Some(caster_turn_commit);
 
-    Ok(())
+    Ok(resources_to_burn)
 }
@@ -1,11 +1,10 @@
 use anchor_lang::{prelude::*, solana_program::sysvar};
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
 use crate::account::*;
 use crate::error::ErrorCode;
-use crate::utils::{EARTH_INDEX, FIRE_INDEX, GAME_CREATOR_AUTHORITY_PUBKEY, is_spell_successful, ItemFeature, ItemType, SpellSnapshot, SpellType, WATER_INDEX};
+use crate::utils::{burn_resources, set_caster_stats_owner, EARTH_INDEX, FIRE_INDEX, GAME_CREATOR_AUTHORITY_PUBKEY, is_spell_successful, ItemFeature, ItemType, SpellSnapshot, SpellType, WATER_INDEX, CASTER_VERSION};
 use crate::utils::TurnCommit;
 
 #[derive(Accounts)]
@@ -22,9 +21,20 @@ pub struct Spell<'info> {
     pub game: Box<Account<'info, Game>>,
     #[account(mut, has_one = authority, has_one = game)]
     pub player: Box<Account<'info, Player>>,
-    #[account(mut, constraint = caster.owner == player.key())]
+    #[account(
+    mut,
+    constraint = caster.owner == player.key(),
+    constraint = caster.version == CASTER_VERSION @ ErrorCode::CasterNotMigrated
+    )]
     pub caster: Box<Account<'info, Caster>>,
 
+    #[account(init_if_needed,
+    seeds = [b"caster_stats", caster.key().as_ref()],
+    bump,
+    payer = authority,
+    space = CasterStats::SIZE)]
+    pub caster_stats: Box<Account<'info, CasterStats>>,
+
     #[account(address = sysvar::slot_hashes::id())]
     pub slots: UncheckedAccount<'info>,
 
@@ -60,10 +70,67 @@ pub struct Spell<'info> {
 
 pub fn caster_commit_spell(ctx: Context<Spell>) -> ProgramResult {
     let game_turn = ctx.accounts.game.turn_info.turn;
+    let is_new_turn = ctx.accounts.caster.turn_commit == None;
+
+    let resources_to_burn = {
+        let slots_ref = ctx.accounts.slots.data.borrow();
+        let slots = &**slots_ref;
+
+        commit_spell(
+            &mut ctx.accounts.caster,
+            &mut ctx.accounts.game_turn_data,
+            game_turn,
+            &ctx.accounts.spellbook,
+            [
+                ctx.accounts.resource_1_token_account.amount,
+                ctx.accounts.resource_2_token_account.amount,
+                ctx.accounts.resource_3_token_account.amount,
+            ],
+            slots,
+        )?
+    };
 
-    let caster = &mut ctx.accounts.caster;
-    let turn_data = &mut ctx.accounts.game_turn_data;
+    let caster_stats = &mut ctx.accounts.caster_stats;
+    set_caster_stats_owner(
+        caster_stats,
+        ctx.accounts.caster.key(),
+        *ctx.bumps.get("caster_stats").unwrap(),
+    );
+
+    caster_stats.spells_cast = caster_stats.spells_cast.saturating_add(1);
+
+    if is_new_turn {
+        caster_stats.turns_played = caster_stats.turns_played.saturating_add(1);
+    }
 
+    burn_resources(
+        ctx.accounts.token_program.to_account_info(),
+        [
+            ctx.accounts.resource_1_mint_account.to_account_info(),
+            ctx.accounts.resource_2_mint_account.to_account_info(),
+            ctx.accounts.resource_3_mint_account.to_account_info(),
+        ],
+        [
+            ctx.accounts.resource_1_token_account.to_account_info(),
+            ctx.accounts.resource_2_token_account.to_account_info(),
+            ctx.accounts.resource_3_token_account.to_account_info(),
+        ],
+        ctx.accounts.authority.to_account_info(),
+        &[],
+        resources_to_burn,
+    )
+}
+
+//Shared with the action queue, validates and adds the spell to the caster's turn commit and
+//returns the resources that need to be burned for it
+pub fn commit_spell(
+    caster: &mut Caster,
+    turn_data: &mut TurnData,
+    game_turn: u32,
+    spellbook: &Item,
+    resource_balances: [u64; 3],
+    slots: &[u8],
+) -> Result<[u64; 3], ProgramError> {
     let mut caster_turn_commit: TurnCommit = match &caster.turn_commit {
         Some(turn_commit) => *turn_commit,
         None => TurnCommit {
@@ -80,92 +147,39 @@ pub fn caster_commit_spell(ctx: Context<Spell>) -> ProgramResult {
         return Err(ErrorCode::ActionAlreadyDone.into());
     }
 
+    let mut resources_to_burn: [u64; 3] = [0, 0, 0];
+
     if let ItemType::SpellBook {
         cost_feature, cost, spell, rarity, ..
-    } = ctx.accounts.spellbook.item_type
+    } = spellbook.item_type
     {
         let resource_burned = cost as u64;
 
         match cost_feature {
-            ItemFeature::Fire => {
-                if ctx.accounts.resource_1_token_account.amount.checked_sub(resource_burned) == None {
-                    return Err(ErrorCode::PlayerIsPoor.into());
-                }
-
-                turn_data.resource_1_burned += resource_burned;
-
-                caster_turn_commit.resources_burned[FIRE_INDEX] = caster_turn_commit.resources_burned[FIRE_INDEX]
-                    .checked_add(resource_burned)
-                    .unwrap();
-
-                token::burn(
-                    CpiContext::new(
-                        ctx.accounts.token_program.to_account_info().clone(),
-                        token::Burn {
-                            mint: ctx.accounts.resource_1_mint_account.to_account_info().clone(),
-                            to: ctx.accounts.resource_1_token_account.to_account_info().clone(), // STUPID NAME: its the token account you are burning the tokens from
-                            authority: ctx.accounts.authority.to_account_info(),
-                        },
-                    ),
-                    resource_burned,
-                )?;
+            ItemFeature::Fire => resources_to_burn[FIRE_INDEX] = resource_burned,
+            ItemFeature::Water => resources_to_burn[WATER_INDEX] = resource_burned,
+            ItemFeature::Earth => resources_to_burn[EARTH_INDEX] = resource_burned,
+            _ => {
+                return Err(ErrorCode::InvalidSpellCost.into());
             }
-            ItemFeature::Water => {
-                if ctx.accounts.resource_2_token_account.amount.checked_sub(resource_burned) == None {
-                    return Err(ErrorCode::PlayerIsPoor.into());
-                }
+        }
 
-                turn_data.resource_2_burned += resource_burned;
-
-                caster_turn_commit.resources_burned[WATER_INDEX] = caster_turn_commit.resources_burned[WATER_INDEX]
-                    .checked_add(resource_burned)
-                    .unwrap();
-
-                token::burn(
-                    CpiContext::new(
-                        ctx.accounts.token_program.to_account_info().clone(),
-                        token::Burn {
-                            mint: ctx.accounts.resource_2_mint_account.to_account_info().clone(),
-                            to: ctx.accounts.resource_2_token_account.to_account_info().clone(), // STUPID NAME: its the token account you are burning the tokens from
-                            authority: ctx.accounts.authority.to_account_info(),
-                        },
-                    ),
-                    resource_burned,
-                )?;
+        for i in 0..resources_to_burn.len() {
+            if resource_balances[i].checked_sub(resources_to_burn[i]) == None {
+                return Err(ErrorCode::PlayerIsPoor.into());
             }
-            ItemFeature::Earth => {
-                if ctx.accounts.resource_3_token_account.amount.checked_sub(resource_burned) == None {
-                    return Err(ErrorCode::PlayerIsPoor.into());
-                }
 
-                turn_data.resource_3_burned += resource_burned;
-
-                caster_turn_commit.resources_burned[EARTH_INDEX] = caster_turn_commit.resources_burned[EARTH_INDEX]
-                    .checked_add(resource_burned)
-                    .unwrap();
-
-                token::burn(
-                    CpiContext::new(
-                        ctx.accounts.token_program.to_account_info().clone(),
-                        token::Burn {
-                            mint: ctx.accounts.resource_3_mint_account.to_account_info().clone(),
-                            to: ctx.accounts.resource_3_token_account.to_account_info().clone(), // STUPID NAME: its the token account you are burning the tokens from
-                            authority: ctx.accounts.authority.to_account_info(),
-                        },
-                    ),
-                    resource_burned,
-                )?;
-            }
-            _ => {
-                return Err(ErrorCode::InvalidSpellCost.into());
-            }
+            caster_turn_commit.resources_burned[i] = caster_turn_commit.resources_burned[i]
+                .checked_add(resources_to_burn[i])
+                .unwrap();
         }
 
+        turn_data.resource_1_burned += resources_to_burn[FIRE_INDEX];
+        turn_data.resource_2_burned += resources_to_burn[WATER_INDEX];
+        turn_data.resource_3_burned += resources_to_burn[EARTH_INDEX];
+
         match spell {
             SpellType::Craft => {
-                let slots_ref = ctx.accounts.slots.data.borrow();
-                let slots = &**slots_ref;
-
                 let mut offset: usize = 1;
 
                 if is_spell_successful(slots, &mut offset, rarity) {
@@ -188,7 +202,7 @@ pub fn caster_commit_spell(ctx: Context<Spell>) -> ProgramResult {
 
     caster_turn_commit.actions.add_new_action_order(1);
 
-    ctx.accounts.caster.turn_commit = Some(caster_turn_commit);
+    caster.turn_commit = Some(caster_turn_commit);
 
-    Ok(())
+    Ok(resources_to_burn)
 }
@@ -4,15 +4,16 @@ use anchor_lang::{prelude::*, solana_program::sysvar};
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token;
 use anchor_spl::token::{Mint, Token, TokenAccount, Transfer};
-use solana_maths::{Rate, TryMul};
 
 use crate::account::*;
 use crate::error::ErrorCode;
 use crate::utils::{
-    generate_new_equipment, get_current_tile, get_player_bonuses,
-    give_exp_to_caster_resources_burned, give_exp_to_caster_spell, is_spell_successful,
-    zombify_account, ItemRarity, ItemType, RandomValue, SpellType, DECIMALS_PRECISION, EARTH_INDEX,
-    FIRE_INDEX, GAME_CREATOR_AUTHORITY_PUBKEY, LADA_DISTRIBUTION_PER_TURN, WATER_INDEX,
+    generate_new_equipment, get_current_tile, get_player_bonuses, get_spell_book_charges_left,
+    is_equipment_contributing,
+    get_lada_reward_for_resources_burned, give_exp_to_caster_resources_burned,
+    give_exp_to_caster_spell, is_spell_successful,
+    mint_resources, set_caster_stats_owner, update_inventory, wear_item, close_item_account, create_item_account, finish_redeem_item, ItemRarity, ItemType, RandomValue, SpellType, EARTH_INDEX,
+    FIRE_INDEX, GAME_CREATOR_AUTHORITY_PUBKEY, MAX_ITEM_LEVEL, WATER_INDEX, CASTER_VERSION,
 };
 use crate::{Tile, TileType};
 
@@ -28,11 +29,24 @@ pub struct Action<'info> {
 
     #[account(mut)]
     pub game: Box<Account<'info, Game>>,
+    #[account(seeds = [b"game_config", game.key().as_ref()], bump)]
+    pub game_config: UncheckedAccount<'info>,
     #[account(mut, has_one = authority, has_one = game)]
     pub player: Box<Account<'info, Player>>,
-    #[account(mut, constraint = caster.owner == player.key())]
+    #[account(
+    mut,
+    constraint = caster.owner == player.key(),
+    constraint = caster.version == CASTER_VERSION @ ErrorCode::CasterNotMigrated
+    )]
     pub caster: Box<Account<'info, Caster>>,
 
+    #[account(init_if_needed,
+    seeds = [b"caster_stats", caster.key().as_ref()],
+    bump,
+    payer = authority,
+    space = CasterStats::SIZE)]
+    pub caster_stats: Box<Account<'info, CasterStats>>,
+
     #[account(mut, seeds = [b"game_signer"], bump)]
     pub game_signer: UncheckedAccount<'info>,
 
@@ -79,30 +93,151 @@ pub struct Action<'info> {
     ], bump = game_turn_data.bump)]
     pub game_turn_data: Box<Account<'info, TurnData>>,
 
-    #[account(init, space =  Item::SIZE, payer = authority)]
-    pub item: Box<Account<'info, Item>>, //This will represent the item that could potentially be created by the casted spell
+    //This will represent the item that could potentially be created by the casted spell, it's
+    //created in the instruction since it's closed when unused and init would write it back on exit
+    #[account(mut, seeds = [b"item", player.key().as_ref(), player.item_counter.to_string().as_ref()], bump)]
+    pub item: UncheckedAccount<'info>,
 
     //There will be a remaining account that represents the spell book item, so that we can
     //zero it out (burn it) at index 0
     //We will also use sub instructions for this,
 
-    // Optional accounts for player bonuses
+    // Optional accounts for player bonuses, mutable since they lose durability when used
+    #[account(mut)]
     pub staff: UncheckedAccount<'info>,
+    #[account(mut)]
     pub head: UncheckedAccount<'info>,
+    #[account(mut)]
     pub robe: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub amulet: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub ring: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"inventory", player.key().as_ref()], bump)]
+    pub inventory: UncheckedAccount<'info>,
 }
 
 //noinspection RsExternalLinter
 pub fn caster_redeem_action<'info>(
     ctx: Context<'_, '_, '_, 'info, Action<'info>>,
 ) -> ProgramResult {
-    let caster = &mut ctx.accounts.caster;
-    let game = &ctx.accounts.game;
-    let player = &ctx.accounts.player;
-    let turn_data = &ctx.accounts.game_turn_data;
+    let game_config =
+        GameConfig::load_or_default(&ctx.accounts.game_config, ctx.accounts.game.key())?;
+
+    set_caster_stats_owner(
+        &mut ctx.accounts.caster_stats,
+        ctx.accounts.caster.key(),
+        *ctx.bumps.get("caster_stats").unwrap(),
+    );
+
+    let mut item = create_item_account(
+        &ctx.accounts.item.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        ctx.program_id,
+        &ctx.accounts.player.key(),
+        ctx.accounts.player.item_counter,
+    )?;
+
+    let is_item_used = redeem_turn_commit(RedeemAccounts {
+        program_id: ctx.program_id,
+        token_program: ctx.accounts.token_program.to_account_info(),
+        game: &ctx.accounts.game,
+        game_config: &game_config,
+        player: &ctx.accounts.player,
+        caster: &mut ctx.accounts.caster,
+        caster_stats: &mut ctx.accounts.caster_stats,
+        game_signer: ctx.accounts.game_signer.to_account_info(),
+        slots: ctx.accounts.slots.to_account_info(),
+        resource_mint_accounts: [
+            ctx.accounts.resource_1_mint_account.to_account_info(),
+            ctx.accounts.resource_2_mint_account.to_account_info(),
+            ctx.accounts.resource_3_mint_account.to_account_info(),
+        ],
+        resource_token_accounts: [
+            ctx.accounts.resource_1_token_account.to_account_info(),
+            ctx.accounts.resource_2_token_account.to_account_info(),
+            ctx.accounts.resource_3_token_account.to_account_info(),
+        ],
+        game_lada_token_account: &ctx.accounts.game_lada_token_account,
+        lada_token_account: ctx.accounts.lada_token_account.to_account_info(),
+        turn_data: &mut ctx.accounts.game_turn_data,
+        item: &mut item,
+        equipment_accounts: [
+            ctx.accounts.staff.to_account_info(),
+            ctx.accounts.head.to_account_info(),
+            ctx.accounts.robe.to_account_info(),
+            ctx.accounts.amulet.to_account_info(),
+            ctx.accounts.ring.to_account_info(),
+        ],
+        spell_book: ctx.remaining_accounts.get(0).cloned(),
+        authority: ctx.accounts.authority.to_account_info(),
+        inventory: ctx.accounts.inventory.to_account_info(),
+    })?;
+
+    //The item PDA is taken even if the item isn't used
+    ctx.accounts.player.item_counter = ctx.accounts.player.item_counter.checked_add(1).unwrap();
+
+    // Burn item if not used
+    finish_redeem_item(
+        item,
+        is_item_used,
+        &ctx.accounts.authority.to_account_info(),
+        ctx.program_id,
+    )?;
+
+    if is_item_used {
+        update_inventory(
+            &ctx.accounts.inventory,
+            &ctx.accounts.player.key(),
+            &[ctx.accounts.item.key()],
+            &[],
+            ctx.program_id,
+        )?;
+    }
+    Ok(())
+}
+
+//Everything needed to redeem a turn commit, so that it can be done by the player or by a keeper
+//running the caster's action queue
+pub struct RedeemAccounts<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub token_program: AccountInfo<'info>,
+    pub game: &'a Account<'info, Game>,
+    pub game_config: &'a GameConfig,
+    pub player: &'a Account<'info, Player>,
+    pub caster: &'a mut Account<'info, Caster>,
+    pub caster_stats: &'a mut Account<'info, CasterStats>,
+    pub game_signer: AccountInfo<'info>,
+    pub slots: AccountInfo<'info>,
+    //FIRE_INDEX, WATER_INDEX, EARTH_INDEX order
+    pub resource_mint_accounts: [AccountInfo<'info>; 3],
+    pub resource_token_accounts: [AccountInfo<'info>; 3],
+    pub game_lada_token_account: &'a Account<'info, TokenAccount>,
+    pub lada_token_account: AccountInfo<'info>,
+    //Turn data of the turn the commit was made in
+    pub turn_data: &'a mut Account<'info, TurnData>,
+    pub item: &'a mut Account<'info, Item>,
+    //Staff, head, robe, amulet and ring, used for player bonuses
+    pub equipment_accounts: [AccountInfo<'info>; 5],
+    pub spell_book: Option<AccountInfo<'info>>,
+    //Receives the rent of the spell book once it's burned
+    pub authority: AccountInfo<'info>,
+    //Optional, the burned spell book is removed from it
+    pub inventory: AccountInfo<'info>,
+}
+
+//Returns true if the item account was populated, if not it's up to the caller to burn it
+pub fn redeem_turn_commit(accounts: RedeemAccounts) -> Result<bool, ProgramError> {
+    let caster = accounts.caster;
+    let caster_stats = accounts.caster_stats;
+    let game = accounts.game;
+    let game_config = accounts.game_config;
+    let player = accounts.player;
+    let turn_data = accounts.turn_data;
 
-    // Flag used to burn item account if not populated
-    let mut burn_item_account = true;
+    let mut is_item_used = false;
 
     match caster.turn_commit {
         None => {
@@ -113,7 +248,7 @@ pub fn caster_redeem_action<'info>(
                 return Err(ErrorCode::SameTurnRedeem.into());
             }
 
-            let slots_ref = ctx.accounts.slots.data.borrow();
+            let slots_ref = accounts.slots.data.borrow();
             let slots = &**slots_ref;
             let mut offset: usize = 1;
 
@@ -126,7 +261,7 @@ pub fn caster_redeem_action<'info>(
                 .filter(|value| **value != 0)
                 .count() as u8;
 
-            let seeds = &[b"game_signer".as_ref(), &[ctx.accounts.game.signer_bump]];
+            let seeds = &[b"game_signer".as_ref(), &[game.signer_bump]];
 
             let signer = &[&seeds[..]];
 
@@ -154,23 +289,34 @@ pub fn caster_redeem_action<'info>(
                                 let range_min_resource: u64 = 1;
                                 let mut range_max_resource: u64 = 10 * (tile_level + 1) as u64; // +1 since 0 based
 
-                                let staff_account: Result<Account<Item>, ProgramError> =
-                                    Account::try_from(&ctx.accounts.staff);
-
-                                let head_account: Result<Account<Item>, ProgramError> =
-                                    Account::try_from(&ctx.accounts.head);
-
-                                let robe_account: Result<Account<Item>, ProgramError> =
-                                    Account::try_from(&ctx.accounts.robe);
+                                let mut equipment_accounts: Vec<Result<Account<Item>, ProgramError>> =
+                                    accounts.equipment_accounts.iter().map(Account::try_from).collect();
 
                                 let player_bonuses = get_player_bonuses(
                                     &caster.modifiers,
-                                    vec![&staff_account, &head_account, &robe_account],
+                                    equipment_accounts.iter().collect(),
                                     &game,
                                     &player,
                                     &caster,
+                                    &game_config.set_bonuses,
                                 );
 
+                                //Equipment that gave bonuses for this loot loses durability
+                                for equipment_account in equipment_accounts.iter_mut() {
+                                    if let Ok(equipment) = equipment_account {
+                                        if is_equipment_contributing(
+                                            &caster.modifiers,
+                                            equipment,
+                                            &game,
+                                            &player,
+                                            &caster,
+                                        ) {
+                                            wear_item(equipment);
+                                            equipment.exit(accounts.program_id)?;
+                                        }
+                                    }
+                                }
+
                                 match looted_tile.tile_type {
                                     TileType::Earth => {
                                         range_max_resource += player_bonuses.earth_chance as u64;
@@ -191,65 +337,66 @@ pub fn caster_redeem_action<'info>(
                                     range_max_resource,
                                 );
 
-                                if u16::random_within_range(slots, &mut offset, 100, 10000)
-                                    < player_bonuses.critical_chance
-                                {
+                                //The roll is always done so a crit potion doesn't change the
+                                //following random values
+                                let is_critical =
+                                    u16::random_within_range(slots, &mut offset, 100, 10000)
+                                        < player_bonuses.critical_chance;
+
+                                if is_critical || turn_commit.actions.consumables.guaranteed_critical {
                                     number_of_resources_given *= 2;
+                                    caster_stats.critical_hits =
+                                        caster_stats.critical_hits.saturating_add(1);
                                 }
 
-                                let resource_token_account: &Account<TokenAccount>;
-                                let resource_mint_account: &Account<Mint>;
+                                let mut resources_to_mint: [u64; 3] = [0, 0, 0];
 
                                 match looted_tile.tile_type {
                                     TileType::Fire => {
-                                        resource_token_account =
-                                            &ctx.accounts.resource_1_token_account;
-                                        resource_mint_account =
-                                            &ctx.accounts.resource_1_mint_account;
+                                        resources_to_mint[FIRE_INDEX] = number_of_resources_given;
                                     }
                                     TileType::Water => {
-                                        resource_token_account =
-                                            &ctx.accounts.resource_2_token_account;
-                                        resource_mint_account =
-                                            &ctx.accounts.resource_2_mint_account;
+                                        resources_to_mint[WATER_INDEX] = number_of_resources_given;
                                     }
                                     TileType::Earth => {
-                                        resource_token_account =
-                                            &ctx.accounts.resource_3_token_account;
-                                        resource_mint_account =
-                                            &ctx.accounts.resource_3_mint_account;
+                                        resources_to_mint[EARTH_INDEX] = number_of_resources_given;
                                     }
                                     _ => {
                                         return Err(ErrorCode::InvalidTileForLooting.into());
                                     }
                                 }
 
-                                token::mint_to(
-                                    CpiContext::new(
-                                        ctx.accounts.token_program.to_account_info().clone(),
-                                        token::MintTo {
-                                            mint: resource_mint_account.to_account_info(),
-                                            to: resource_token_account.to_account_info(),
-                                            authority: ctx.accounts.game_signer.to_account_info(),
-                                        },
-                                    )
-                                    .with_signer(signer),
-                                    number_of_resources_given,
+                                mint_resources(
+                                    accounts.token_program.clone(),
+                                    accounts.resource_mint_accounts.clone(),
+                                    accounts.resource_token_accounts.clone(),
+                                    accounts.game_signer.clone(),
+                                    signer,
+                                    resources_to_mint,
                                 )?;
 
+                                for i in 0..resources_to_mint.len() {
+                                    caster_stats.resources_looted[i] = caster_stats.resources_looted[i]
+                                        .saturating_add(resources_to_mint[i]);
+                                }
+
                                 //Chance of finding a chest is 10% on a resource tile
                                 match looted_tile.tile_type {
                                     TileType::Fire | TileType::Water | TileType::Earth => {
                                         //default is 10% so 1000 since we work in % (to not have floating)
-                                        let magic_find_chance =
+                                        let mut magic_find_chance =
                                             1000 + player_bonuses.magic_find_chance;
 
+                                        if turn_commit.actions.consumables.double_chest_chance {
+                                            magic_find_chance = magic_find_chance.saturating_mul(2);
+                                        }
+
                                         if u16::random_within_range(slots, &mut offset, 100, 10000)
                                             < magic_find_chance
                                         {
-                                            let item = &mut ctx.accounts.item;
+                                            let item = &mut *accounts.item;
                                             item.game = game.key();
-                                            item.owner = ctx.accounts.player.key();
+                                            item.owner = player.key();
                                             item.equipped_owner = None;
                                             item.item_type = ItemType::Chest {
                                                 tier: match tile_level {
@@ -262,7 +409,10 @@ pub fn caster_redeem_action<'info>(
                                             };
                                             //Since 0 based, +1
                                             item.level = tile_level + 1;
-                                            burn_item_account = false;
+                                            is_item_used = true;
+
+                                            caster_stats.chests_found =
+                                                caster_stats.chests_found.saturating_add(1);
                                         }
                                     }
                                     _ => {}
@@ -270,27 +420,25 @@ pub fn caster_redeem_action<'info>(
                             }
                             1 => {
                                 //Spell
-                                if ctx.remaining_accounts.len() == 0 {
-                                    return Err(ErrorCode::SpellAccountMissing.into());
-                                }
-
-                                match ctx.remaining_accounts.get(0) {
+                                let spell_book_account = match &accounts.spell_book {
                                     None => {
-                                        return Err(ErrorCode::ProvidedSpellBookIsNull.into());
+                                        return Err(ErrorCode::SpellAccountMissing.into());
                                     }
-                                    _ => {}
-                                }
+                                    Some(spell_book_account) => spell_book_account,
+                                };
 
-                                let spell_book_account =
-                                    &mut ctx.remaining_accounts.get(0).unwrap();
-
-                                if spell_book_account.key() != caster.modifiers.spell_book.unwrap()
+                                if caster.modifiers.spell_book == None
+                                    || spell_book_account.key() != caster.modifiers.spell_book.unwrap()
                                 {
                                     return Err(ErrorCode::SpellKeyMismatch.into());
                                 }
 
-                                let mut spell_book_account: &mut Account<Item> =
-                                    &mut Account::try_from(&spell_book_account.clone()).unwrap();
+                                let mut spell_book_account: Account<Item> =
+                                    Account::try_from(spell_book_account)?;
+
+                                //Failed casts use a charge too
+                                let charges_left =
+                                    get_spell_book_charges_left(&spell_book_account).saturating_sub(1);
 
                                 if let ItemType::SpellBook {
                                     spell,
@@ -299,112 +447,76 @@ pub fn caster_redeem_action<'info>(
                                     ..
                                 } = spell_book_account.item_type
                                 {
+
                                     if is_spell_successful(slots, &mut offset, rarity) {
+                                        caster_stats.spells_succeeded =
+                                            caster_stats.spells_succeeded.saturating_add(1);
+
+                                        let mut resources_to_mint: [u64; 3] = [0, 0, 0];
+
                                         match spell {
                                             SpellType::Fire => {
-                                                token::mint_to(
-                                                    CpiContext::new(
-                                                        ctx.accounts
-                                                            .token_program
-                                                            .to_account_info()
-                                                            .clone(),
-                                                        token::MintTo {
-                                                            mint: ctx
-                                                                .accounts
-                                                                .resource_1_mint_account
-                                                                .to_account_info(),
-                                                            to: ctx
-                                                                .accounts
-                                                                .resource_1_token_account
-                                                                .to_account_info(),
-                                                            authority: ctx
-                                                                .accounts
-                                                                .game_signer
-                                                                .to_account_info(),
-                                                        },
-                                                    )
-                                                    .with_signer(signer),
-                                                    value as u64,
-                                                )?;
+                                                resources_to_mint[FIRE_INDEX] = value as u64;
                                             }
                                             SpellType::Water => {
-                                                token::mint_to(
-                                                    CpiContext::new(
-                                                        ctx.accounts
-                                                            .token_program
-                                                            .to_account_info()
-                                                            .clone(),
-                                                        token::MintTo {
-                                                            mint: ctx
-                                                                .accounts
-                                                                .resource_2_mint_account
-                                                                .to_account_info(),
-                                                            to: ctx
-                                                                .accounts
-                                                                .resource_2_token_account
-                                                                .to_account_info(),
-                                                            authority: ctx
-                                                                .accounts
-                                                                .game_signer
-                                                                .to_account_info(),
-                                                        },
-                                                    )
-                                                    .with_signer(signer),
-                                                    value as u64,
-                                                )?;
+                                                resources_to_mint[WATER_INDEX] = value as u64;
                                             }
                                             SpellType::Earth => {
-                                                token::mint_to(
-                                                    CpiContext::new(
-                                                        ctx.accounts
-                                                            .token_program
-                                                            .to_account_info()
-                                                            .clone(),
-                                                        token::MintTo {
-                                                            mint: ctx
-                                                                .accounts
-                                                                .resource_3_mint_account
-                                                                .to_account_info(),
-                                                            to: ctx
-                                                                .accounts
-                                                                .resource_3_token_account
-                                                                .to_account_info(),
-                                                            authority: ctx
-                                                                .accounts
-                                                                .game_signer
-                                                                .to_account_info(),
-                                                        },
-                                                    )
-                                                    .with_signer(signer),
-                                                    value as u64,
-                                                )?;
+                                                resources_to_mint[EARTH_INDEX] = value as u64;
                                             }
                                             SpellType::Experience => {
-                                                give_exp_to_caster_spell(caster, value as u64);
+                                                give_exp_to_caster_spell(
+                                                    caster,
+                                                    value as u64,
+                                                    &game_config.experience_curve,
+                                                );
                                             }
                                             SpellType::Item => {
-                                                let item = &mut ctx.accounts.item;
                                                 generate_new_equipment(
-                                                    item,
-                                                    &ctx.accounts.game,
-                                                    &ctx.accounts.player,
+                                                    accounts.item,
+                                                    game,
+                                                    player,
                                                     spell_book_account.level,
                                                     Some(rarity),
                                                     slots,
                                                     &mut offset,
                                                 )?;
-                                                burn_item_account = false;
+                                                is_item_used = true;
                                             }
                                             _ => {}
                                         }
+
+                                        mint_resources(
+                                            accounts.token_program.clone(),
+                                            accounts.resource_mint_accounts.clone(),
+                                            accounts.resource_token_accounts.clone(),
+                                            accounts.game_signer.clone(),
+                                            signer,
+                                            resources_to_mint,
+                                        )?;
                                     }
                                 }
 
-                                zombify_account(
-                                    &mut spell_book_account,
-                                    ctx.accounts.authority.to_account_info(),
-                                    ctx.program_id,
-                                )?;
+                                if charges_left > 0 {
+                                    spell_book_account.spell_book_charges = charges_left;
+                                    spell_book_account.exit(accounts.program_id)?;
+                                } else {
+                                    close_item_account(
+                                        &spell_book_account,
+                                        accounts.authority.clone(),
+                                    )?;
+
+                                    update_inventory(
+                                        &accounts.inventory,
+                                        &player.key(),
+                                        &[],
+                                        &[spell_book_account.key()],
+                                        accounts.program_id,
+                                    )?;
+
+                                    //The spell book is gone, free the slot so a new one can be equipped
+                                    caster.modifiers.spell_book = None;
+                                }
                             }
                             2 => {
                                 //Move
@@ -416,6 +528,19 @@ pub fn caster_redeem_action<'info>(
                                 let crafting_snapshot = turn_commit.actions.crafting.unwrap();
                                 let spell_snapshot = turn_commit.actions.spell.clone();
 
+                                let equipment_accounts: Vec<Result<Account<Item>, ProgramError>> =
+                                    accounts.equipment_accounts.iter().map(Account::try_from).collect();
+
+                                let crafting_chance = get_player_bonuses(
+                                    &caster.modifiers,
+                                    equipment_accounts.iter().collect(),
+                                    &game,
+                                    &player,
+                                    &caster,
+                                    &game_config.set_bonuses,
+                                )
+                                .crafting_chance;
+
                                 //Item level or rarity has a 10% chance of going up
                                 let mut new_item_level = crafting_snapshot.min_level;
                                 let mut new_item_rarity = crafting_snapshot.min_rarity;
@@ -423,13 +548,18 @@ pub fn caster_redeem_action<'info>(
                                 //If you have a spell that increases the level, it defaults to common for rarity
                                 if spell_snapshot != None
                                     && spell_snapshot.unwrap().is_extra_level_bonus
-                                    && crafting_snapshot.min_level < 30
+                                    && crafting_snapshot.min_level < MAX_ITEM_LEVEL
                                 {
                                     new_item_level += 1;
                                     new_item_rarity = ItemRarity::Common;
-                                } else if u8::random_within_range(slots, &mut offset, 0, 10) == 5 {
+                                } else if u8::random_within_range(slots, &mut offset, 0, 10) == 5
+                                    //Crafting luck gives a second chance at the upgrade
+                                    || (crafting_chance > 0
+                                        && u16::random_within_range(slots, &mut offset, 100, 10000)
+                                        < crafting_chance)
+                                {
                                     if u8::random_within_range(slots, &mut offset, 1, 2) == 1
-                                        && crafting_snapshot.min_level < 30
+                                        && crafting_snapshot.min_level < MAX_ITEM_LEVEL
                                     {
                                         new_item_level += 1;
                                         new_item_rarity = ItemRarity::Common;
@@ -452,9 +582,8 @@ pub fn caster_redeem_action<'info>(
                                         }
                                     }
                                 }
-                                let item = &mut ctx.accounts.item;
                                 generate_new_equipment(
-                                    item,
+                                    accounts.item,
                                     game,
                                     player,
                                     new_item_level,
@@ -462,7 +591,10 @@ pub fn caster_redeem_action<'info>(
                                     slots,
                                     &mut offset,
                                 )?;
-                                burn_item_account = false;
+                                is_item_used = true;
+
+                                caster_stats.items_crafted =
+                                    caster_stats.items_crafted.saturating_add(1);
                             }
                             _ => {}
                         }
@@ -480,72 +612,50 @@ pub fn caster_redeem_action<'info>(
                 Some(turn_commit.resources_burned[FIRE_INDEX]),
                 Some(turn_commit.resources_burned[EARTH_INDEX]),
                 Some(turn_commit.resources_burned[WATER_INDEX]),
+                &game_config.experience_curve,
             );
 
+            let lada_to_distribute = turn_data
+                .get_lada_to_distribute(&game_config.get_emission_schedule(), turn_commit.turn);
+
             //Send LADA tokens based on proportion of resources burned by the user vs total resources
-            let fire_resources_burned = turn_data.resource_1_burned;
-            let water_resources_burned = turn_data.resource_2_burned;
-            let earth_resources_burned = turn_data.resource_3_burned;
-
-            let proportion_burned_by_user: f64 = (turn_commit.resources_burned[FIRE_INDEX]
-                / non_zero(fire_resources_burned)
-                + turn_commit.resources_burned[EARTH_INDEX] / non_zero(earth_resources_burned)
-                + turn_commit.resources_burned[WATER_INDEX] / non_zero(water_resources_burned))
-                as f64
-                / 3.0;
-
-            //Precision of 9 decimals DECIMALS_PRECISION
-            let scaled_proportion_burned_by_user =
-                (proportion_burned_by_user * DECIMALS_PRECISION as f64) as u64;
-
-            let amount = Rate::from_scaled_val(scaled_proportion_burned_by_user)
-                .try_mul(LADA_DISTRIBUTION_PER_TURN)
-                .unwrap()
-                .try_mul(DECIMALS_PRECISION)
-                .unwrap()
-                .try_round_u64()
-                .unwrap();
+            let lada_reward = get_lada_reward_for_resources_burned(
+                turn_commit.resources_burned,
+                [
+                    turn_data.resource_1_burned,
+                    turn_data.resource_2_burned,
+                    turn_data.resource_3_burned,
+                ],
+                game_config.get_lada_distribution_weights(),
+                lada_to_distribute,
+            )?;
+
+            //Rounding is always down, this only guards the total given for the turn, and the
+            //game's LADA can run out before every caster redeemed
+            let amount = lada_reward
+                .min(lada_to_distribute.saturating_sub(turn_data.lada_distributed))
+                .min(accounts.game_lada_token_account.amount);
+
+            turn_data.lada_distributed = turn_data.lada_distributed.checked_add(amount).unwrap();
 
             let cpi_accounts = Transfer {
-                from: ctx
-                    .accounts
-                    .game_lada_token_account
-                    .to_account_info()
-                    .clone(),
-                to: ctx.accounts.lada_token_account.to_account_info().clone(),
-                authority: ctx.accounts.game_signer.to_account_info().clone(),
+                from: accounts.game_lada_token_account.to_account_info(),
+                to: accounts.lada_token_account.clone(),
+                authority: accounts.game_signer.clone(),
             };
 
-            let transfer_cpi = CpiContext::new(
-                ctx.accounts.token_program.to_account_info().clone(),
-                cpi_accounts,
-            );
+            let transfer_cpi = CpiContext::new(accounts.token_program.clone(), cpi_accounts);
 
-            let seeds = &[b"game_signer".as_ref(), &[ctx.accounts.game.signer_bump]];
-            let signer = &[&seeds[..]];
+            if amount > 0 {
+                token::transfer(transfer_cpi.with_signer(signer), amount)?;
+            }
 
-            token::transfer(transfer_cpi.with_signer(signer), amount)?;
+            caster_stats.lada_earned = caster_stats.lada_earned.saturating_add(amount);
 
             //Reset caster's turn commit
             caster.turn_commit = None;
         }
     }
 
-    // Burn item if not used
-    if burn_item_account {
-        let item = &mut ctx.accounts.item;
-        zombify_account(
-            item,
-            ctx.accounts.authority.to_account_info(),
-            ctx.program_id,
-        )?;
-    }
-    Ok(())
-}
-
-pub fn non_zero(number: u64) -> u64 {
-    match number {
-        0 => 1,
-        _ => number,
-    }
+    Ok(is_item_used)
 }
@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+
+use crate::account::*;
+use crate::error::ErrorCode;
+use crate::utils::{set_caster_stats_owner, update_inventory, ConsumableType, ItemType, TurnCommit, CASTER_VERSION};
+
+#[derive(Accounts)]
+#[instruction(consumable_type: ConsumableType)]
+pub struct StackConsumable<'info> {
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(has_one = authority, has_one = game)]
+    pub player: Account<'info, Player>,
+
+    #[account(init_if_needed,
+    seeds = [b"consumable_balance", player.key().as_ref(), &[consumable_type as u8]],
+    bump,
+    payer = authority,
+    space = ConsumableBalance::SIZE)]
+    pub consumable_balance: Box<Account<'info, ConsumableBalance>>,
+
+    #[account(
+    mut,
+    close = authority,
+    constraint = item.game == game.key(),
+    constraint = item.owner == player.key(),
+    )]
+    pub item: Box<Account<'info, Item>>,
+
+    #[account(mut, seeds = [b"inventory", player.key().as_ref()], bump)]
+    pub inventory: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(consumable_type: ConsumableType)]
+pub struct UseConsumable<'info> {
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(has_one = authority, has_one = game)]
+    pub player: Account<'info, Player>,
+
+    #[account(
+    mut,
+    constraint = caster.owner == player.key(),
+    constraint = caster.version == CASTER_VERSION @ ErrorCode::CasterNotMigrated
+    )]
+    pub caster: Box<Account<'info, Caster>>,
+
+    #[account(init_if_needed,
+    seeds = [b"caster_stats", caster.key().as_ref()],
+    bump,
+    payer = authority,
+    space = CasterStats::SIZE)]
+    pub caster_stats: Box<Account<'info, CasterStats>>,
+
+    #[account(mut,
+    seeds = [b"consumable_balance", player.key().as_ref(), &[consumable_type as u8]],
+    bump = consumable_balance.bump,
+    has_one = player)]
+    pub consumable_balance: Box<Account<'info, ConsumableBalance>>,
+}
+
+pub fn stack_consumable(
+    ctx: Context<StackConsumable>,
+    consumable_type: ConsumableType,
+) -> ProgramResult {
+    if ctx.accounts.item.item_type != (ItemType::Consumable { consumable_type }) {
+        return Err(ErrorCode::ItemIsNotAConsumable.into());
+    }
+
+    let consumable_balance = &mut ctx.accounts.consumable_balance;
+    consumable_balance.bump = *ctx.bumps.get("consumable_balance").unwrap();
+    consumable_balance.player = ctx.accounts.player.key();
+    consumable_balance.consumable_type = consumable_type;
+    consumable_balance.amount = consumable_balance.amount.checked_add(1).unwrap();
+
+    update_inventory(
+        &ctx.accounts.inventory,
+        &ctx.accounts.player.key(),
+        &[],
+        &[ctx.accounts.item.key()],
+        ctx.program_id,
+    )?;
+
+    //The item account is closed, its rent goes back to the player
+    Ok(())
+}
+
+pub fn use_consumable(ctx: Context<UseConsumable>, consumable_type: ConsumableType) -> ProgramResult {
+    let game_turn = ctx.accounts.game.turn_info.turn;
+    let caster = &mut ctx.accounts.caster;
+    let is_new_turn = caster.turn_commit == None;
+
+    if ctx.accounts.consumable_balance.amount == 0 {
+        return Err(ErrorCode::NotEnoughConsumables.into());
+    }
+
+    let mut caster_turn_commit: TurnCommit = match caster.turn_commit.clone() {
+        Some(turn_commit) => turn_commit,
+        None => TurnCommit {
+            turn: game_turn,
+            ..Default::default()
+        },
+    };
+
+    if caster_turn_commit.turn != game_turn {
+        return Err(ErrorCode::PendingTurn.into());
+    }
+
+    if caster_turn_commit.actions.consumables.is_active(consumable_type) {
+        return Err(ErrorCode::ConsumableAlreadyUsed.into());
+    }
+
+    //The move cost is burned on commit, so the scroll has to be used before moving
+    if consumable_type == ConsumableType::MoveScroll && caster_turn_commit.actions.mv != None {
+        return Err(ErrorCode::ActionAlreadyDone.into());
+    }
+
+    caster_turn_commit.actions.consumables.activate(consumable_type);
+    caster.turn_commit = Some(caster_turn_commit);
+
+    ctx.accounts.consumable_balance.amount -= 1;
+
+    let caster_stats = &mut ctx.accounts.caster_stats;
+    set_caster_stats_owner(
+        caster_stats,
+        ctx.accounts.caster.key(),
+        *ctx.bumps.get("caster_stats").unwrap(),
+    );
+
+    if is_new_turn {
+        caster_stats.turns_played = caster_stats.turns_played.saturating_add(1);
+    }
+
+    Ok(())
+}
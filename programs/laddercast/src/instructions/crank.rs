@@ -1,8 +1,9 @@
 use core::mem::size_of;
 
 use anchor_lang::{prelude::*, solana_program::sysvar};
+use anchor_spl::token::TokenAccount;
 
-use crate::account::{Game, TurnData};
+use crate::account::{Game, GameConfig, TurnData};
 use crate::error::ErrorCode;
 use crate::event::NewTurn;
 use crate::utils::{cycle_tile, get_highest_level_and_column, GAME_CREATOR_AUTHORITY_PUBKEY};
@@ -17,6 +18,11 @@ pub struct Crank<'info> {
 
     #[account(mut)]
     pub game_account: Box<Account<'info, Game>>,
+    #[account(seeds = [b"game_config", game_account.key().as_ref()], bump)]
+    pub game_config: UncheckedAccount<'info>,
+
+    #[account(constraint = game_lada_token_account.key() == game_account.lada_token_account)]
+    pub game_lada_token_account: Box<Account<'info, TokenAccount>>,
 
     #[account(address = sysvar::slot_hashes::id())]
     pub slots: UncheckedAccount<'info>,
@@ -34,6 +40,10 @@ pub struct Crank<'info> {
 }
 
 pub fn crank(ctx: Context<Crank>) -> ProgramResult {
+    let game_config = GameConfig::load_or_default(
+        &ctx.accounts.game_config,
+        ctx.accounts.game_account.key(),
+    )?;
     let game = &mut ctx.accounts.game_account;
 
     // if game.authority.to_string() != GAME_CREATOR_AUTHORITY_PUBKEY {
@@ -52,6 +62,14 @@ pub fn crank(ctx: Context<Crank>) -> ProgramResult {
 
     turn_data.bump = *ctx.bumps.get("game_turn_data").unwrap();
 
+    //Once the game's LADA runs low the turns only give what is left
+    turn_data.set_lada_to_distribute(
+        game_config
+            .get_emission_schedule()
+            .get_lada_for_turn(game.turn_info.turn + 1)
+            .min(ctx.accounts.game_lada_token_account.amount),
+    );
+
     let current_game_turn_data = &mut ctx.accounts.current_game_turn_data;
 
     current_game_turn_data.map = game.map.clone();
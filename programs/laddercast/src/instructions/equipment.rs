@@ -2,7 +2,9 @@ use anchor_lang::prelude::*;
 
 use crate::account::{Caster, Game, Item, Player};
 use crate::error::ErrorCode;
-use crate::utils::{EquipmentType, ItemType};
+use crate::utils::{
+    equip_item_to_caster, set_caster_loadout, unequip_item_from_caster, CASTER_VERSION,
+};
 
 #[derive(Accounts)]
 pub struct EquipUnequipItem<'info> {
@@ -18,6 +20,7 @@ pub struct EquipUnequipItem<'info> {
     #[account(
     mut,
     constraint = item.owner == player.key(),
+    constraint = caster.version == CASTER_VERSION @ ErrorCode::CasterNotMigrated
     )]
     pub caster: Account<'info, Caster>,
     #[account(
@@ -28,101 +31,67 @@ pub struct EquipUnequipItem<'info> {
     pub item: Box<Account<'info, Item>>,
 }
 
-pub fn equip_item(ctx: Context<EquipUnequipItem>) -> ProgramResult {
-    let caster = &mut ctx.accounts.caster;
-    let item = &mut ctx.accounts.item;
-
-    if item.equipped_owner != None {
-        return Err(ErrorCode::ItemAlreadyInUse.into());
-    }
-
-    if item.level > caster.level {
-        return Err(ErrorCode::ItemLevelTooHigh.into());
-    }
-
-    if caster.turn_commit != None {
-        return Err(ErrorCode::NoEquipUnequipOnPendingTurn.into());
-    }
-
-    item.equipped_owner = Some(caster.key());
-
-    match item.item_type {
-        ItemType::Equipment {
-            feature: _,
-            rarity: _,
-            equipment_type,
-            value: _,
-        } => match equipment_type {
-            EquipmentType::Head => {
-                if caster.modifiers.head != None {
-                    return Err(ErrorCode::ItemTypeAlreadyEquipped.into());
-                }
-
-                caster.modifiers.head = Some(item.key());
-            }
-            EquipmentType::Staff => {
-                if caster.modifiers.staff != None {
-                    return Err(ErrorCode::ItemTypeAlreadyEquipped.into());
-                }
-
-                caster.modifiers.staff = Some(item.key());
-            }
-            EquipmentType::Robe => {
-                if caster.modifiers.robe != None {
-                    return Err(ErrorCode::ItemTypeAlreadyEquipped.into());
-                }
+#[derive(Accounts)]
+pub struct SetLoadout<'info> {
+    pub game: Box<Account<'info, Game>>,
+    pub authority: Signer<'info>,
+    #[account(has_one = authority, has_one = game)]
+    pub player: Account<'info, Player>,
+    #[account(
+    mut,
+    constraint = caster.owner == player.key(),
+    constraint = caster.version == CASTER_VERSION @ ErrorCode::CasterNotMigrated
+    )]
+    pub caster: Account<'info, Caster>,
 
-                caster.modifiers.robe = Some(item.key());
-            }
-        },
-        ItemType::SpellBook { .. } => {
-            if caster.modifiers.spell_book != None {
-                return Err(ErrorCode::ItemTypeAlreadyEquipped.into());
-            }
+    // New item of each slot, the default pubkey (system program) leaves the slot empty
+    // Not marked as mut since the system program can't be writable, the items still have to be
+    pub head: UncheckedAccount<'info>,
+    pub robe: UncheckedAccount<'info>,
+    pub staff: UncheckedAccount<'info>,
+    pub amulet: UncheckedAccount<'info>,
+    pub ring: UncheckedAccount<'info>,
+    pub spell_book: UncheckedAccount<'info>,
+    //The items currently equipped by the caster are passed as remaining accounts, in any order,
+    //unless they are also part of the new loadout
+}
 
-            caster.modifiers.spell_book = Some(item.key());
-        }
-        _ => {
-            return Err(ErrorCode::InvalidEquipItemType.into());
-        }
-    }
+pub fn equip_item(ctx: Context<EquipUnequipItem>) -> ProgramResult {
+    let caster_key = ctx.accounts.caster.key();
+    let item_key = ctx.accounts.item.key();
 
-    Ok(())
+    equip_item_to_caster(&mut ctx.accounts.caster, caster_key, &mut ctx.accounts.item, item_key)
 }
 
 pub fn unequip_item(ctx: Context<EquipUnequipItem>) -> ProgramResult {
-    let caster = &mut ctx.accounts.caster;
-    let item = &mut ctx.accounts.item;
+    let caster_key = ctx.accounts.caster.key();
 
-    if item.equipped_owner == None || item.equipped_owner.unwrap() != caster.key() {
-        return Err(ErrorCode::ItemNotExists.into());
-    }
+    unequip_item_from_caster(&mut ctx.accounts.caster, caster_key, &mut ctx.accounts.item)
+}
 
-    if caster.turn_commit != None {
+pub fn set_loadout<'info>(ctx: Context<'_, '_, '_, 'info, SetLoadout<'info>>) -> ProgramResult {
+    if ctx.accounts.caster.turn_commit != None {
         return Err(ErrorCode::NoEquipUnequipOnPendingTurn.into());
     }
 
-    match item.item_type {
-        ItemType::Equipment { equipment_type, .. } => match equipment_type {
-            EquipmentType::Head => {
-                caster.modifiers.head = None;
-            }
-            EquipmentType::Robe => {
-                caster.modifiers.robe = None;
-            }
-            EquipmentType::Staff => {
-                caster.modifiers.staff = None;
-            }
-        },
-        ItemType::SpellBook { .. } => {
-            caster.modifiers.spell_book = None;
-        }
-        _ => {
-            return Err(ErrorCode::InvalidEquipItemType.into());
-        }
-    }
-
-    item.equipped_owner = None;
-
-    Ok(())
+    //Same order as Modifiers::get_loadout
+    let new_item_accounts = [
+        ctx.accounts.head.to_account_info(),
+        ctx.accounts.robe.to_account_info(),
+        ctx.accounts.staff.to_account_info(),
+        ctx.accounts.amulet.to_account_info(),
+        ctx.accounts.ring.to_account_info(),
+        ctx.accounts.spell_book.to_account_info(),
+    ];
+    let caster_key = ctx.accounts.caster.key();
+
+    set_caster_loadout(
+        &mut ctx.accounts.caster,
+        caster_key,
+        ctx.accounts.game.key(),
+        ctx.accounts.player.key(),
+        &new_item_accounts,
+        ctx.remaining_accounts,
+        ctx.program_id,
+    )
 }
@@ -0,0 +1,416 @@
+use anchor_lang::{prelude::*, solana_program::sysvar};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::account::*;
+use crate::error::ErrorCode;
+use crate::instructions::{
+    commit_craft, commit_loot, commit_move, commit_spell, redeem_turn_commit, RedeemAccounts,
+};
+use crate::utils::{
+    burn_resources, check_equipment_accounts, close_item_account, create_item_account,
+    finish_redeem_item, set_caster_stats_owner, spend_action_queue_budget, update_inventory,
+    CASTER_VERSION,
+};
+
+#[derive(Accounts)]
+pub struct ExecuteQueuedTurn<'info> {
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+
+    //Anyone can execute a queued turn, they only pay for the item account
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(mut)]
+    pub game: Box<Account<'info, Game>>,
+    #[account(seeds = [b"game_config", game.key().as_ref()], bump)]
+    pub game_config: UncheckedAccount<'info>,
+    #[account(mut, has_one = game)]
+    pub player: Box<Account<'info, Player>>,
+    //Wallet of the player, receives the rent of the burned items
+    #[account(mut, constraint = player_authority.key() == player.authority)]
+    pub player_authority: UncheckedAccount<'info>,
+    #[account(
+    mut,
+    constraint = caster.owner == player.key(),
+    constraint = caster.version == CASTER_VERSION @ ErrorCode::CasterNotMigrated
+    )]
+    pub caster: Box<Account<'info, Caster>>,
+
+    #[account(init_if_needed,
+    seeds = [b"caster_stats", caster.key().as_ref()],
+    bump,
+    payer = keeper,
+    space = CasterStats::SIZE)]
+    pub caster_stats: Box<Account<'info, CasterStats>>,
+
+    #[account(mut, seeds = [b"action_queue", caster.key().as_ref()], bump = action_queue.bump)]
+    pub action_queue: Box<Account<'info, ActionQueue>>,
+
+    #[account(mut, seeds = [b"game_signer"], bump)]
+    pub game_signer: UncheckedAccount<'info>,
+
+    #[account(address = sysvar::slot_hashes::id())]
+    pub slots: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = resource_1_mint_account.to_account_info().key() == game.resource_1_mint_account)]
+    pub resource_1_mint_account: Box<Account<'info, Mint>>,
+    #[account(mut, constraint = resource_2_mint_account.to_account_info().key() == game.resource_2_mint_account)]
+    pub resource_2_mint_account: Box<Account<'info, Mint>>,
+    #[account(mut, constraint = resource_3_mint_account.to_account_info().key() == game.resource_3_mint_account)]
+    pub resource_3_mint_account: Box<Account<'info, Mint>>,
+
+    //The game signer was approved as delegate on these when the queue was set
+    #[account(mut,
+    associated_token::mint = resource_1_mint_account,
+    associated_token::authority = player_authority)]
+    pub resource_1_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut,
+    associated_token::mint = resource_2_mint_account,
+    associated_token::authority = player_authority)]
+    pub resource_2_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut,
+    associated_token::mint = resource_3_mint_account,
+    associated_token::authority = player_authority)]
+    pub resource_3_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = game_lada_token_account.key() == game.lada_token_account)]
+    pub game_lada_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = lada_token_account.owner == player.authority, constraint = lada_token_account.mint == game.lada_mint_account)]
+    pub lada_token_account: Box<Account<'info, TokenAccount>>,
+
+    //Turn of the pending commit, this is the current turn data when there is nothing to redeem
+    #[account(mut, seeds = [
+    b"turn_data",
+    game.to_account_info().key().as_ref(),
+    (caster.turn_commit.map(|turn_commit| turn_commit.turn).unwrap_or(game.turn_info.turn)).to_string().as_ref()
+    ], bump = previous_game_turn_data.bump)]
+    pub previous_game_turn_data: Box<Account<'info, TurnData>>,
+
+    #[account(mut, seeds = [b"turn_data", game.to_account_info().key().as_ref(), game.turn_info.turn.to_string().as_ref()], bump = game_turn_data.bump)]
+    pub game_turn_data: Box<Account<'info, TurnData>>,
+
+    //This will represent the item that could potentially be created by the redeem, it's created
+    //in the instruction since it's closed when unused and init would write it back on exit
+    #[account(mut, seeds = [b"item", player.key().as_ref(), player.item_counter.to_string().as_ref()], bump)]
+    pub item: UncheckedAccount<'info>,
+
+    //Accounts of the caster's equipped items, placeholders for the empty slots, mutable since
+    //they lose durability when used
+    #[account(mut)]
+    pub staff: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub head: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub robe: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub amulet: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub ring: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"inventory", player.key().as_ref()], bump)]
+    pub inventory: UncheckedAccount<'info>,
+    //The equipped spell book and the planned crafting items are passed as remaining accounts,
+    //in any order
+}
+
+pub fn execute_queued_turn<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteQueuedTurn<'info>>,
+) -> ProgramResult {
+    let map = ctx.accounts.game.map.clone();
+    let game_turn = ctx.accounts.game.turn_info.turn;
+    let game_config =
+        GameConfig::load_or_default(&ctx.accounts.game_config, ctx.accounts.game.key())?;
+
+    //Only one plan per turn, if the player already committed this turn the keeper has nothing to do
+    if let Some(turn_commit) = ctx.accounts.caster.turn_commit {
+        if turn_commit.turn == game_turn {
+            return Err(ErrorCode::TurnAlreadyCommitted.into());
+        }
+    }
+
+    if ctx.accounts.action_queue.plans.is_empty() {
+        return Err(ErrorCode::ActionQueueEmpty.into());
+    }
+
+    let plan = ctx.accounts.action_queue.plans.remove(0);
+
+    let remaining_accounts = ctx.remaining_accounts;
+
+    let find_remaining_account = |key: Pubkey| -> Option<AccountInfo<'info>> {
+        remaining_accounts
+            .iter()
+            .find(|account| account.key() == key)
+            .cloned()
+    };
+
+    set_caster_stats_owner(
+        &mut ctx.accounts.caster_stats,
+        ctx.accounts.caster.key(),
+        *ctx.bumps.get("caster_stats").unwrap(),
+    );
+
+    let mut item = create_item_account(
+        &ctx.accounts.item.to_account_info(),
+        &ctx.accounts.keeper.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        ctx.program_id,
+        &ctx.accounts.player.key(),
+        ctx.accounts.player.item_counter,
+    )?;
+
+    let mut is_item_used = false;
+
+    if ctx.accounts.caster.turn_commit != None {
+        //The keeper can't leave out the equipped items to drop the caster's bonuses
+        check_equipment_accounts(
+            &ctx.accounts.caster.modifiers,
+            [
+                ctx.accounts.staff.key(),
+                ctx.accounts.head.key(),
+                ctx.accounts.robe.key(),
+                ctx.accounts.amulet.key(),
+                ctx.accounts.ring.key(),
+            ],
+        )?;
+
+        let spell_book = ctx
+            .accounts
+            .caster
+            .modifiers
+            .spell_book
+            .and_then(find_remaining_account);
+
+        if ctx.accounts.caster.modifiers.spell_book != None && spell_book.is_none() {
+            return Err(ErrorCode::SpellAccountMissing.into());
+        }
+
+        is_item_used = redeem_turn_commit(RedeemAccounts {
+            program_id: ctx.program_id,
+            token_program: ctx.accounts.token_program.to_account_info(),
+            game: &ctx.accounts.game,
+            game_config: &game_config,
+            player: &ctx.accounts.player,
+            caster: &mut ctx.accounts.caster,
+            caster_stats: &mut ctx.accounts.caster_stats,
+            game_signer: ctx.accounts.game_signer.to_account_info(),
+            slots: ctx.accounts.slots.to_account_info(),
+            resource_mint_accounts: [
+                ctx.accounts.resource_1_mint_account.to_account_info(),
+                ctx.accounts.resource_2_mint_account.to_account_info(),
+                ctx.accounts.resource_3_mint_account.to_account_info(),
+            ],
+            resource_token_accounts: [
+                ctx.accounts.resource_1_token_account.to_account_info(),
+                ctx.accounts.resource_2_token_account.to_account_info(),
+                ctx.accounts.resource_3_token_account.to_account_info(),
+            ],
+            game_lada_token_account: &ctx.accounts.game_lada_token_account,
+            lada_token_account: ctx.accounts.lada_token_account.to_account_info(),
+            turn_data: &mut ctx.accounts.previous_game_turn_data,
+            item: &mut item,
+            equipment_accounts: [
+                ctx.accounts.staff.to_account_info(),
+                ctx.accounts.head.to_account_info(),
+                ctx.accounts.robe.to_account_info(),
+                ctx.accounts.amulet.to_account_info(),
+                ctx.accounts.ring.to_account_info(),
+            ],
+            spell_book,
+            authority: ctx.accounts.player_authority.to_account_info(),
+            inventory: ctx.accounts.inventory.to_account_info(),
+        })?;
+
+        //Redeem minted resources, balances need to be up to date for the new commit
+        ctx.accounts.resource_1_token_account.reload()?;
+        ctx.accounts.resource_2_token_account.reload()?;
+        ctx.accounts.resource_3_token_account.reload()?;
+    }
+
+    //Each action can only spend what the previous actions of the plan left
+    let mut resource_balances = [
+        ctx.accounts.resource_1_token_account.amount,
+        ctx.accounts.resource_2_token_account.amount,
+        ctx.accounts.resource_3_token_account.amount,
+    ];
+
+    let mut resources_to_burn: [u64; 3] = [0, 0, 0];
+    let mut crafted_away_items: Vec<Pubkey> = vec![];
+
+    //Spell first so that a craft spell applies to the planned crafting, and move before
+    //loot / craft since they are done on the destination tile
+    if plan.spell {
+        let spell_book_account = match ctx
+            .accounts
+            .caster
+            .modifiers
+            .spell_book
+            .and_then(find_remaining_account)
+        {
+            None => {
+                return Err(ErrorCode::SpellAccountMissing.into());
+            }
+            Some(spell_book_account) => spell_book_account,
+        };
+
+        let spell_book: Account<Item> = Account::try_from(&spell_book_account)?;
+
+        if spell_book.game != ctx.accounts.game.key()
+            || spell_book.owner != ctx.accounts.player.key()
+            || spell_book.equipped_owner != Some(ctx.accounts.caster.key())
+        {
+            return Err(ErrorCode::SpellKeyMismatch.into());
+        }
+
+        let slots_ref = ctx.accounts.slots.data.borrow();
+        let slots = &**slots_ref;
+
+        ctx.accounts.caster_stats.spells_cast =
+            ctx.accounts.caster_stats.spells_cast.saturating_add(1);
+
+        let resources_burned = commit_spell(
+            &mut ctx.accounts.caster,
+            &mut ctx.accounts.game_turn_data,
+            game_turn,
+            &spell_book,
+            resource_balances,
+            slots,
+        )?;
+
+        spend_resources(&mut resource_balances, &mut resources_to_burn, resources_burned);
+    }
+
+    if let Some([lvl, clm]) = plan.mv {
+        let resources_burned = commit_move(
+            &mut ctx.accounts.caster,
+            &mut ctx.accounts.game_turn_data,
+            &map,
+            game_turn,
+            lvl,
+            clm,
+            resource_balances,
+        )?;
+
+        spend_resources(&mut resource_balances, &mut resources_to_burn, resources_burned);
+    }
+
+    if plan.loot {
+        commit_loot(&mut ctx.accounts.caster, &map, game_turn)?;
+    }
+
+    if let Some(item_keys) = plan.crafting {
+        let mut items: Vec<Account<Item>> = vec![];
+
+        for item_key in item_keys.iter() {
+            let item_account = match find_remaining_account(*item_key) {
+                None => {
+                    return Err(ErrorCode::QueuedItemMissing.into());
+                }
+                Some(item_account) => item_account,
+            };
+
+            let item: Account<Item> = Account::try_from(&item_account)?;
+
+            if item.game != ctx.accounts.game.key()
+                || item.owner != ctx.accounts.player.key()
+                || item.equipped_owner != None
+            {
+                return Err(ErrorCode::InvalidItemType.into());
+            }
+
+            items.push(item);
+        }
+
+        let resources_burned = commit_craft(
+            &mut ctx.accounts.caster,
+            &mut ctx.accounts.game_turn_data,
+            &map,
+            game_turn,
+            [&items[0], &items[1], &items[2]],
+            resource_balances,
+        )?;
+
+        spend_resources(&mut resource_balances, &mut resources_to_burn, resources_burned);
+
+        for item in items.iter() {
+            crafted_away_items.push(item.key());
+
+            close_item_account(item, ctx.accounts.player_authority.to_account_info())?;
+        }
+    }
+
+    if ctx.accounts.caster.turn_commit != None {
+        ctx.accounts.caster_stats.turns_played =
+            ctx.accounts.caster_stats.turns_played.saturating_add(1);
+    }
+
+    spend_action_queue_budget(
+        &mut ctx.accounts.player,
+        &mut ctx.accounts.action_queue,
+        resources_to_burn,
+    )?;
+
+    let seeds = &[b"game_signer".as_ref(), &[ctx.accounts.game.signer_bump]];
+    let signer = &[&seeds[..]];
+
+    //Game signer burns as the delegate of the player's resource token accounts
+    burn_resources(
+        ctx.accounts.token_program.to_account_info(),
+        [
+            ctx.accounts.resource_1_mint_account.to_account_info(),
+            ctx.accounts.resource_2_mint_account.to_account_info(),
+            ctx.accounts.resource_3_mint_account.to_account_info(),
+        ],
+        [
+            ctx.accounts.resource_1_token_account.to_account_info(),
+            ctx.accounts.resource_2_token_account.to_account_info(),
+            ctx.accounts.resource_3_token_account.to_account_info(),
+        ],
+        ctx.accounts.game_signer.to_account_info(),
+        signer,
+        resources_to_burn,
+    )?;
+
+    //The item PDA is taken even if the item isn't used
+    ctx.accounts.player.item_counter = ctx.accounts.player.item_counter.checked_add(1).unwrap();
+
+    let mut created_items: Vec<Pubkey> = vec![];
+
+    if is_item_used {
+        created_items.push(item.key());
+    }
+
+    // Burn item if not used
+    finish_redeem_item(
+        item,
+        is_item_used,
+        &ctx.accounts.keeper.to_account_info(),
+        ctx.program_id,
+    )?;
+
+    update_inventory(
+        &ctx.accounts.inventory,
+        &ctx.accounts.player.key(),
+        &created_items,
+        &crafted_away_items,
+        ctx.program_id,
+    )?;
+
+    Ok(())
+}
+
+fn spend_resources(
+    resource_balances: &mut [u64; 3],
+    resources_to_burn: &mut [u64; 3],
+    resources_burned: [u64; 3],
+) {
+    for i in 0..resources_burned.len() {
+        //Commits already checked the balances
+        resource_balances[i] -= resources_burned[i];
+        resources_to_burn[i] += resources_burned[i];
+    }
+}
@@ -7,8 +7,8 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 use crate::account::{Caster, Game, Player};
 use crate::error::ErrorCode;
 use crate::utils::{
-    Modifiers, RandomValue, COST_IN_LADA_FOR_CASTER, DECIMALS_PRECISION,
-    GAME_CREATOR_AUTHORITY_PUBKEY,
+    Modifiers, RandomValue, SkillAllocation, CASTER_VERSION, COST_IN_LADA_FOR_CASTER,
+    DECIMALS_PRECISION, GAME_CREATOR_AUTHORITY_PUBKEY,
 };
 
 #[derive(Accounts)]
@@ -49,10 +49,14 @@ pub fn init_caster(ctx: Context<InitCaster>) -> ProgramResult {
     let caster = &mut ctx.accounts.caster;
 
     caster.owner = player.key();
-    caster.version = 1;
+    caster.version = CASTER_VERSION;
     caster.level = 1;
     caster.experience = 0;
     caster.turn_commit = None;
+    caster.prestige = 0;
+    caster.name = [0; 32];
+    caster.skill_points = 0;
+    caster.skills = SkillAllocation::default();
     caster.modifiers = Modifiers {
         tile_level: 0,
         tile_column: u8::random_within_range(slots, &mut offset, 0, 2),
@@ -60,6 +64,8 @@ pub fn init_caster(ctx: Context<InitCaster>) -> ProgramResult {
         robe: None,
         staff: None,
         spell_book: None,
+        amulet: None,
+        ring: None,
     };
 
     //Decimal precision is 9
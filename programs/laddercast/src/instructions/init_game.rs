@@ -7,7 +7,7 @@ use strum::{EnumCount, EnumIter};
 use crate::account::*;
 use crate::error::ErrorCode;
 use crate::utils::constants::*;
-use crate::utils::cycle_tile;
+use crate::utils::{cycle_tile, ExperienceCurve};
 
 #[derive(Accounts)]
 #[instruction(turn_info: GameTurnInfo)]
@@ -36,6 +36,14 @@ pub struct InitGame<'info> {
     )]
     pub game_turn_data: Box<Account<'info, TurnData>>,
 
+    #[account(init,
+    seeds = [b"game_config", game_account.key().as_ref()],
+    bump,
+    payer = authority,
+    space = GameConfig::SIZE
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
     #[account(address = sysvar::slot_hashes::id())]
     pub slots: UncheckedAccount<'info>,
 
@@ -104,9 +112,18 @@ pub fn init_game(
     ctx: Context<InitGame>,
     turn_info: GameTurnInfo,
     merkle_root_nft: [u8; 32],
+    experience_curve: Option<ExperienceCurve>,
 ) -> ProgramResult {
     let game = &mut ctx.accounts.game_account;
     let turn_data = &mut ctx.accounts.game_turn_data;
+    let game_config = &mut ctx.accounts.game_config;
+
+    //Defaults to the original 30 levels table
+    let experience_curve = experience_curve.unwrap_or_default();
+
+    if !experience_curve.is_valid() {
+        return Err(ErrorCode::InvalidExperienceCurve.into());
+    }
 
     // if ctx.accounts.authority.key().to_string() != GAME_CREATOR_AUTHORITY_PUBKEY {
     //     return Err(ErrorCode::NotSuperAdmin.into());
@@ -129,6 +146,16 @@ pub fn init_game(
     game.signer_bump = *ctx.bumps.get("game_signer").unwrap();
     turn_data.bump = *ctx.bumps.get("game_turn_data").unwrap();
 
+    ***game_config = GameConfig::new(game.key(), *ctx.bumps.get("game_config").unwrap());
+    game_config.experience_curve = experience_curve;
+
+    turn_data.set_lada_to_distribute(
+        game_config
+            .get_emission_schedule()
+            .get_lada_for_turn(game.turn_info.turn)
+            .min(ctx.accounts.lada_token_account.amount),
+    );
+
     let slots_ref = ctx.accounts.slots.data.borrow();
     let slots = &**slots_ref;
 
@@ -24,7 +24,7 @@ pub struct InitPlayer<'info> {
     pub player_account: Account<'info, Player>,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy, Default)]
 pub struct PlayerBonuses {
     //Is 0-100 which represents a %
     pub critical_chance: u16,
@@ -36,6 +36,8 @@ pub struct PlayerBonuses {
     pub water_chance: u16,
     //Is a value, not a %
     pub earth_chance: u16,
+    //Is in basis points, 100 represents 1%
+    pub crafting_chance: u16,
 }
 
 pub fn init_player(ctx: Context<InitPlayer>) -> ProgramResult {
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::account::*;
+use crate::error::ErrorCode;
+use crate::utils::add_inventory_item;
+
+#[derive(Accounts)]
+pub struct InitInventory<'info> {
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(has_one = authority, has_one = game)]
+    pub player: Box<Account<'info, Player>>,
+
+    #[account(init,
+    seeds = [b"inventory", player.key().as_ref()],
+    bump,
+    payer = authority,
+    space = Inventory::SIZE)]
+    pub inventory: Box<Account<'info, Inventory>>,
+    //The items the player already owns can be passed as remaining accounts to index them
+}
+
+pub fn init_inventory<'info>(
+    ctx: Context<'_, '_, '_, 'info, InitInventory<'info>>,
+) -> ProgramResult {
+    let inventory = &mut ctx.accounts.inventory;
+    inventory.bump = *ctx.bumps.get("inventory").unwrap();
+    inventory.player = ctx.accounts.player.key();
+
+    for item_info in ctx.remaining_accounts.iter() {
+        let item: Account<Item> = Account::try_from(item_info)?;
+
+        if item.game != ctx.accounts.game.key() || item.owner != ctx.accounts.player.key() {
+            return Err(ErrorCode::ItemNotOwned.into());
+        }
+
+        if !add_inventory_item(inventory, item.key()) {
+            return Err(ErrorCode::InventoryFull.into());
+        }
+    }
+
+    Ok(())
+}
@@ -5,7 +5,7 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 
 use crate::account::*;
 use crate::error::ErrorCode;
-use crate::utils::{EARTH_INDEX, FIRE_INDEX, GAME_CREATOR_AUTHORITY_PUBKEY, give_exp_to_caster_resources_burned, ItemFeature, TurnCommit, WATER_INDEX};
+use crate::utils::{EARTH_INDEX, FIRE_INDEX, GAME_CREATOR_AUTHORITY_PUBKEY, give_exp_to_caster_resources_burned, ItemFeature, TurnCommit, WATER_INDEX, CASTER_VERSION};
 
 #[derive(Accounts)]
 pub struct ManualResourceBurn<'info> {
@@ -18,10 +18,16 @@ pub struct ManualResourceBurn<'info> {
     pub authority: Signer<'info>,
 
     pub game: Box<Account<'info, Game>>,
+    #[account(seeds = [b"game_config", game.key().as_ref()], bump)]
+    pub game_config: UncheckedAccount<'info>,
 
     #[account(mut, has_one = authority, has_one = game)]
     pub player: Box<Account<'info, Player>>,
-    #[account(mut, constraint = caster.owner == player.key())]
+    #[account(
+    mut,
+    constraint = caster.owner == player.key(),
+    constraint = caster.version == CASTER_VERSION @ ErrorCode::CasterNotMigrated
+    )]
     pub caster: Box<Account<'info, Caster>>,
 
     #[account(mut, constraint = resource_1_mint_account.to_account_info().key() == game.resource_1_mint_account)]
@@ -56,6 +62,8 @@ pub fn manual_resource_burn(
     resource_type: ItemFeature,
     amount_to_burn: u64,
 ) -> ProgramResult {
+    let game_config =
+        GameConfig::load_or_default(&ctx.accounts.game_config, ctx.accounts.game.key())?;
     // let game_turn = ctx.accounts.game.turn_info.turn;
     let turn_data = &mut ctx.accounts.game_turn_data;
     let caster = &mut ctx.accounts.caster;
@@ -84,7 +92,13 @@ pub fn manual_resource_burn(
                 return Err(ErrorCode::PlayerIsPoor.into());
             }
 
-            give_exp_to_caster_resources_burned(caster, Some(amount_to_burn), None, None);
+            give_exp_to_caster_resources_burned(
+                caster,
+                Some(amount_to_burn),
+                None,
+                None,
+                &game_config.experience_curve,
+            );
 
             turn_data.resource_1_burned += amount_to_burn;
 
@@ -102,7 +116,13 @@ pub fn manual_resource_burn(
                 return Err(ErrorCode::PlayerIsPoor.into());
             }
 
-            give_exp_to_caster_resources_burned(caster, None, None, Some(amount_to_burn));
+            give_exp_to_caster_resources_burned(
+                caster,
+                None,
+                None,
+                Some(amount_to_burn),
+                &game_config.experience_curve,
+            );
 
             turn_data.resource_2_burned += amount_to_burn;
 
@@ -120,7 +140,13 @@ pub fn manual_resource_burn(
                 return Err(ErrorCode::PlayerIsPoor.into());
             }
 
-            give_exp_to_caster_resources_burned(caster, None, Some(amount_to_burn), None);
+            give_exp_to_caster_resources_burned(
+                caster,
+                None,
+                Some(amount_to_burn),
+                None,
+                &game_config.experience_curve,
+            );
 
             turn_data.resource_3_burned += amount_to_burn;
 
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_lang::{AccountSerialize, Discriminator};
+
+use crate::account::*;
+use crate::error::ErrorCode;
+use crate::utils::{migrate_legacy_caster, LegacyCaster};
+
+//Version 1 casters can't be loaded with the current layout, so the accounts are read by hand
+//Migrations don't change any value, so anyone can run them
+#[derive(Accounts)]
+pub struct MigrateCaster<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub caster: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateCasterMetadata<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub nft_metadata: UncheckedAccount<'info>,
+}
+
+//Fields of the metadata before the caster, they are the same in every version
+#[derive(AnchorSerialize, AnchorDeserialize)]
+struct MetadataNFTCasterHeader {
+    self_bump: u8,
+    mint_bump: u8,
+    mint: Pubkey,
+}
+
+fn is_legacy_account(account: &AccountInfo, program_id: &Pubkey, discriminator: [u8; 8]) -> bool {
+    let data = account.data.borrow();
+
+    account.owner == program_id && data.len() > 8 && data[..8] == discriminator
+}
+
+//The version is the first field of every caster layout
+fn read_legacy_caster(data: &mut &[u8]) -> Result<Caster, ProgramError> {
+    match data.first() {
+        Some(1) => Ok(migrate_legacy_caster(&LegacyCaster::deserialize(data)?)),
+        _ => Err(ErrorCode::InvalidCasterMigration.into()),
+    }
+}
+
+pub fn migrate_caster(ctx: Context<MigrateCaster>) -> ProgramResult {
+    let caster_account = ctx.accounts.caster.to_account_info();
+
+    if !is_legacy_account(&caster_account, ctx.program_id, Caster::discriminator()) {
+        return Err(ErrorCode::InvalidCasterMigration.into());
+    }
+
+    let mut data = caster_account.try_borrow_mut_data()?;
+    let caster = read_legacy_caster(&mut &data[8..])?;
+
+    caster.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+pub fn migrate_caster_metadata(ctx: Context<MigrateCasterMetadata>) -> ProgramResult {
+    let metadata_account = ctx.accounts.nft_metadata.to_account_info();
+
+    if !is_legacy_account(
+        &metadata_account,
+        ctx.program_id,
+        MetadataNFTCaster::discriminator(),
+    ) {
+        return Err(ErrorCode::InvalidCasterMigration.into());
+    }
+
+    let mut data = metadata_account.try_borrow_mut_data()?;
+    let mut legacy_data: &[u8] = &data[8..];
+    let header = MetadataNFTCasterHeader::deserialize(&mut legacy_data)?;
+    let caster = read_legacy_caster(&mut legacy_data)?;
+
+    MetadataNFTCaster {
+        self_bump: header.self_bump,
+        mint_bump: header.mint_bump,
+        mint: header.mint,
+        caster: MetadataCaster {
+            version: caster.version,
+            level: caster.level,
+            experience: caster.experience,
+            owner: caster.owner,
+            modifiers: caster.modifiers,
+            turn_commit: caster.turn_commit,
+            prestige: caster.prestige,
+            name: caster.name,
+            skill_points: caster.skill_points,
+            skills: caster.skills,
+        },
+    }
+    .try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
@@ -10,14 +10,15 @@ use mpl_token_metadata::state::Creator;
 use spl_token::instruction::AuthorityType;
 
 use crate::account::{
-    Caster, Game, Item, MetadataCaster, MetadataItem, MetadataNFTCaster, MetadataNFTItem, Player,
+    Caster, Game, GameConfig, Item, MetadataCaster, MetadataItem, MetadataNFTCaster,
+    MetadataNFTItem, Player,
 };
 use crate::error::ErrorCode;
 use crate::utils::{
-    get_merkle_string_for_caster, get_merkle_string_for_item, get_name_for_mint,
-    verify_merkle_proof, MetaplexTokenMetadata, EXPERIENCE_REQUIRED_PER_LEVEL,
-    GAME_CREATOR_AUTHORITY_PUBKEY, NFT_CASTER_NAME, NFT_CREATOR_PUBKEY,
-    NFT_CREATOR_SPLITTER_PUBKEY, NFT_MINT_DESCRIPTION,
+    close_action_queue, get_caster_name, get_merkle_string_for_caster, get_merkle_string_for_item,
+    get_name_for_mint, reset_caster_experience_for_mint, update_inventory, verify_merkle_proof,
+    MetaplexTokenMetadata, GAME_CREATOR_AUTHORITY_PUBKEY, NFT_CASTER_NAME, NFT_CREATOR_PUBKEY,
+    NFT_CREATOR_SPLITTER_PUBKEY, NFT_MINT_DESCRIPTION, CASTER_VERSION,
 };
 
 #[derive(Accounts)]
@@ -66,6 +67,9 @@ pub struct MintItem<'info> {
     space = MetadataNFTItem::SIZE
     )]
     pub nft_metadata: Box<Account<'info, MetadataNFTItem>>,
+
+    #[account(mut, seeds = [b"inventory", player.key().as_ref()], bump)]
+    pub inventory: UncheckedAccount<'info>,
 }
 
 pub fn mint_item(
@@ -103,6 +107,10 @@ pub fn mint_item(
         level: item.level,
         item_type: item.item_type.clone(),
         equipped_owner: None,
+        reforge_count: item.reforge_count,
+        wear: item.wear,
+        secondary_stat: item.secondary_stat,
+        spell_book_charges: item.spell_book_charges,
     };
 
     ctx.accounts.nft_metadata.self_bump = *ctx.bumps.get("nft_metadata").unwrap();
@@ -185,6 +193,14 @@ pub fn mint_item(
         None,
     )?;
 
+    update_inventory(
+        &ctx.accounts.inventory,
+        &ctx.accounts.player.key(),
+        &[],
+        &[ctx.accounts.item.key()],
+        ctx.program_id,
+    )?;
+
     Ok(())
 }
 
@@ -198,6 +214,8 @@ pub struct MintCaster<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     pub game: Box<Account<'info, Game>>,
+    #[account(seeds = [b"game_config", game.key().as_ref()], bump)]
+    pub game_config: UncheckedAccount<'info>,
 
     #[account(mut, seeds = [b"game_signer"], bump)]
     pub game_signer: UncheckedAccount<'info>,
@@ -206,10 +224,15 @@ pub struct MintCaster<'info> {
     pub player: Box<Account<'info, Player>>,
     #[account(mut,
     close = authority,
-    constraint = caster.owner == player.key()
+    constraint = caster.owner == player.key(),
+    constraint = caster.version == CASTER_VERSION @ ErrorCode::CasterNotMigrated
     )]
     pub caster: Box<Account<'info, Caster>>,
 
+    //Might not exist if the caster never had planned turns
+    #[account(mut, seeds = [b"action_queue", caster.key().as_ref()], bump)]
+    pub action_queue: UncheckedAccount<'info>,
+
     #[account(mut)]
     // Where you write the stuff
     pub metaplex_metadata_account: UncheckedAccount<'info>,
@@ -239,25 +262,19 @@ pub fn mint_caster(
     nft_uri: String,
     merkle_proof: Vec<[u8; 32]>,
 ) -> ProgramResult {
+    let game_config =
+        GameConfig::load_or_default(&ctx.accounts.game_config, ctx.accounts.game.key())?;
+
     //create metadata
     let mut caster = **ctx.accounts.caster.clone();
 
-    //To prevent infinite number of nft metadata, we set the experience to the min for the current level
-    caster.experience = EXPERIENCE_REQUIRED_PER_LEVEL[(caster.level - 1) as usize]; //-1 since 0 based array
+    reset_caster_experience_for_mint(&mut caster, &game_config.experience_curve);
 
     if caster.turn_commit != None {
         return Err(ErrorCode::InvalidCasterMintPendingTurn.into());
     }
 
-    if [
-        caster.modifiers.robe,
-        caster.modifiers.staff,
-        caster.modifiers.head,
-        caster.modifiers.spell_book,
-    ]
-    .iter()
-    .any(|item_pub| *item_pub != None)
-    {
+    if caster.modifiers.has_equipped_items() {
         return Err(ErrorCode::InvalidCasterMintEquipped.into());
     }
 
@@ -281,6 +298,10 @@ pub fn mint_caster(
         owner: caster.owner,
         modifiers: caster.modifiers.clone(),
         turn_commit: None,
+        prestige: caster.prestige,
+        name: caster.name,
+        skill_points: caster.skill_points,
+        skills: caster.skills,
     };
 
     ctx.accounts.nft_metadata.self_bump = *ctx.bumps.get("nft_metadata").unwrap();
@@ -300,6 +321,9 @@ pub fn mint_caster(
         ctx.accounts.rent.to_account_info(),
     ];
 
+    //Renamed casters keep their name on the NFT
+    let name = get_caster_name(&caster).unwrap_or(NFT_CASTER_NAME.to_string());
+
     anchor_lang::solana_program::program::invoke(
         &create_metadata_accounts_v2(
             *ctx.accounts.metaplex_token_metadata_program.key,
@@ -308,7 +332,7 @@ pub fn mint_caster(
             *ctx.accounts.authority.key,
             *ctx.accounts.authority.key,
             *ctx.accounts.game_signer.key,
-            NFT_CASTER_NAME.to_string(),
+            name,
             NFT_MINT_DESCRIPTION.to_string(),
             nft_uri,
             Some(vec![
@@ -357,5 +381,13 @@ pub fn mint_caster(
         None,
     )?;
 
+    //Turns planned for the caster can't be executed once it's an NFT
+    close_action_queue(
+        &mut ctx.accounts.player,
+        &ctx.accounts.action_queue.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        ctx.program_id,
+    )?;
+
     Ok(())
 }
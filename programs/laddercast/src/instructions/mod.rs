@@ -1,33 +1,65 @@
 pub use burn_nft::*;
+pub use caster_cancel_action::*;
 pub use caster_commit_craft::*;
 pub use caster_commit_loot::*;
 pub use caster_commit_move::*;
 pub use caster_commit_spell::*;
 pub use caster_turn_redeem::*;
+pub use consumables::*;
 pub use crank::*;
 pub use equipment::*;
+pub use execute_queued_turn::*;
 pub use init_caster::*;
 pub use init_game::*;
 pub use init_player::*;
+pub use inventory::*;
 pub use manual_resource_burn::*;
+pub use migrate_caster::*;
 pub use mint_nft::*;
 pub use open_chest::*;
+pub use open_chests_batch::*;
+pub use prestige_caster::*;
+pub use reforge_item::*;
+pub use rename_caster::*;
+pub use repair_item::*;
+pub use retire_caster::*;
+pub use salvage_item::*;
+pub use set_action_queue::*;
+pub use skills::*;
 pub use test_helper::*;
+pub use transfer_caster::*;
+pub use update_game_config::*;
 pub use update_merkle_root::*;
 
 pub mod burn_nft;
+pub mod caster_cancel_action;
 pub mod caster_commit_craft;
 pub mod caster_commit_loot;
 pub mod caster_commit_move;
 pub mod caster_commit_spell;
 pub mod caster_turn_redeem;
+pub mod consumables;
 pub mod crank;
 pub mod equipment;
+pub mod execute_queued_turn;
 pub mod init_caster;
 pub mod init_game;
 pub mod init_player;
+pub mod inventory;
 pub mod manual_resource_burn;
+pub mod migrate_caster;
 pub mod mint_nft;
 pub mod open_chest;
+pub mod open_chests_batch;
+pub mod prestige_caster;
+pub mod reforge_item;
+pub mod rename_caster;
+pub mod repair_item;
+pub mod retire_caster;
+pub mod salvage_item;
+pub mod set_action_queue;
+pub mod skills;
 pub mod test_helper;
+pub mod transfer_caster;
+pub mod update_game_config;
 pub mod update_merkle_root;
@@ -1,25 +1,71 @@
-use core::mem::size_of;
+use core::slice::Iter;
 
 use anchor_lang::{prelude::*, solana_program::sysvar};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token;
+use anchor_spl::token::{Mint, Token, TokenAccount, Transfer};
 
 use crate::account::*;
 use crate::error::ErrorCode;
-use crate::utils::zombify_account;
-use crate::utils::{generate_new_equipment, generate_new_spell_book, ItemType, RandomValue};
+use crate::utils::{
+    create_item_account, generate_new_consumable, generate_new_equipment, generate_new_spell_book,
+    get_chest_lada, get_item_rarity_with_floor, get_resources_for_feature, mint_resources, update_inventory,
+    ItemFeature, ItemType, LootDrop, RandomEnumValue, RandomValue,
+    DECIMALS_PRECISION,
+};
 
 #[derive(Accounts)]
 pub struct OpenChest<'info> {
     pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
 
     pub game: Box<Account<'info, Game>>,
+    #[account(seeds = [b"game_config", game.key().as_ref()], bump)]
+    pub game_config: UncheckedAccount<'info>,
     #[account(mut)]
     pub authority: Signer<'info>,
     #[account(mut, has_one = authority, has_one = game)]
-    pub player: Account<'info, Player>,
+    pub player: Box<Account<'info, Player>>,
+    #[account(mut, seeds = [b"game_signer"], bump)]
+    pub game_signer: UncheckedAccount<'info>,
 
     #[account(address = sysvar::slot_hashes::id())]
     pub slots: UncheckedAccount<'info>,
 
+    #[account(mut, constraint = resource_1_mint_account.to_account_info().key() == game.resource_1_mint_account)]
+    pub resource_1_mint_account: Box<Account<'info, Mint>>,
+    #[account(mut, constraint = resource_2_mint_account.to_account_info().key() == game.resource_2_mint_account)]
+    pub resource_2_mint_account: Box<Account<'info, Mint>>,
+    #[account(mut, constraint = resource_3_mint_account.to_account_info().key() == game.resource_3_mint_account)]
+    pub resource_3_mint_account: Box<Account<'info, Mint>>,
+
+    #[account(init_if_needed,
+    associated_token::mint = resource_1_mint_account,
+    associated_token::authority = authority,
+    payer = authority)]
+    pub resource_1_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(init_if_needed,
+    associated_token::mint = resource_2_mint_account,
+    associated_token::authority = authority,
+    payer = authority)]
+    pub resource_2_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(init_if_needed,
+    associated_token::mint = resource_3_mint_account,
+    associated_token::authority = authority,
+    payer = authority)]
+    pub resource_3_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = game_lada_token_account.key() == game.lada_token_account)]
+    pub game_lada_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = lada_token_account.mint == game.lada_mint_account)]
+    pub lada_token_account: Box<Account<'info, TokenAccount>>,
+    //Chest LADA comes out of the LADA of the current turn
+    #[account(mut, seeds = [b"turn_data", game.to_account_info().key().as_ref(), game.turn_info.turn.to_string().as_ref()], bump = game_turn_data.bump)]
+    pub game_turn_data: Box<Account<'info, TurnData>>,
+
     #[account(
     mut,
     close = authority,
@@ -28,83 +74,270 @@ pub struct OpenChest<'info> {
     )]
     pub chest: Box<Account<'info, Item>>,
 
-    #[account(init, space = Item::SIZE, payer = authority)]
-    pub item_1: Box<Account<'info, Item>>,
-    #[account(init, space = Item::SIZE, payer = authority)]
-    pub item_2: Box<Account<'info, Item>>,
-    #[account(init, space = Item::SIZE, payer = authority)]
-    pub item_3: Box<Account<'info, Item>>,
+    #[account(mut, seeds = [b"inventory", player.key().as_ref()], bump)]
+    pub inventory: UncheckedAccount<'info>,
+    //The new items are created in the remaining accounts, which need to be the next item PDAs of
+    //the player, as many as the max items of the chest's loot table (unused ones are left untouched)
 }
 
-pub fn open_chest(ctx: Context<OpenChest>) -> ProgramResult {
-    let player = &ctx.accounts.player;
-    let game = &ctx.accounts.game;
-    let chest = &ctx.accounts.chest;
-
-    let mut item_to_create = vec![
-        &mut ctx.accounts.item_1,
-        &mut ctx.accounts.item_2,
-        &mut ctx.accounts.item_3,
-    ];
-
-    match chest.item_type {
-        ItemType::Chest { .. } => {
-            let min_item_level = match chest.item_type {
-                ItemType::Chest { tier } => match tier {
-                    1 => 1,
-                    2 => 6,
-                    3 => 11,
-                    4 => 16,
-                    _ => 1,
-                },
-                _ => 1,
-            };
-            let max_item_level = chest.level;
-
-            let slots_ref = ctx.accounts.slots.data.borrow();
-            let slots = &**slots_ref;
-            let mut offset: usize = 1;
-
-            //Item level of chest gives range 1 to item level
-            // tier sets the minimum, tier 1 = 1, tier 2 = 6
-            //Each chest generates 3 new item
-            for i in 0..3 {
+//Everything needed to give the content of chests, shared by the single and batch openings
+pub struct ChestAccounts<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub game: &'a Account<'info, Game>,
+    pub game_config: &'a GameConfig,
+    pub player: &'a Account<'info, Player>,
+    //Pays for the new item accounts
+    pub authority: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub game_signer: AccountInfo<'info>,
+    //FIRE_INDEX, WATER_INDEX, EARTH_INDEX order
+    pub resource_mint_accounts: [AccountInfo<'info>; 3],
+    pub resource_token_accounts: [AccountInfo<'info>; 3],
+    pub game_lada_token_account: &'a Account<'info, TokenAccount>,
+    pub lada_token_account: AccountInfo<'info>,
+}
+
+pub fn open_chest<'info>(ctx: Context<'_, '_, '_, 'info, OpenChest<'info>>) -> ProgramResult {
+    let game_config =
+        GameConfig::load_or_default(&ctx.accounts.game_config, ctx.accounts.game.key())?;
+    let slots_ref = ctx.accounts.slots.data.borrow();
+    let slots = &**slots_ref;
+    let mut offset: usize = 1;
+
+    let chest_accounts = ChestAccounts {
+        program_id: ctx.program_id,
+        game: &ctx.accounts.game,
+        game_config: &game_config,
+        player: &ctx.accounts.player,
+        authority: ctx.accounts.authority.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        game_signer: ctx.accounts.game_signer.to_account_info(),
+        resource_mint_accounts: [
+            ctx.accounts.resource_1_mint_account.to_account_info(),
+            ctx.accounts.resource_2_mint_account.to_account_info(),
+            ctx.accounts.resource_3_mint_account.to_account_info(),
+        ],
+        resource_token_accounts: [
+            ctx.accounts.resource_1_token_account.to_account_info(),
+            ctx.accounts.resource_2_token_account.to_account_info(),
+            ctx.accounts.resource_3_token_account.to_account_info(),
+        ],
+        game_lada_token_account: &ctx.accounts.game_lada_token_account,
+        lada_token_account: ctx.accounts.lada_token_account.to_account_info(),
+    };
+
+    let mut item_counter = ctx.accounts.player.item_counter;
+    let mut created_items: Vec<Pubkey> = vec![];
+
+    let (resources_to_mint, lada_to_transfer) = roll_chest_loot(
+        &chest_accounts,
+        &ctx.accounts.chest,
+        &mut ctx.remaining_accounts.iter(),
+        &mut item_counter,
+        &mut created_items,
+        slots,
+        &mut offset,
+    )?;
+
+    give_chest_rewards(
+        &chest_accounts,
+        &mut ctx.accounts.game_turn_data,
+        resources_to_mint,
+        lada_to_transfer,
+    )?;
+
+    ctx.accounts.player.item_counter = item_counter;
+
+    update_inventory(
+        &ctx.accounts.inventory,
+        &ctx.accounts.player.key(),
+        &created_items,
+        &[ctx.accounts.chest.key()],
+        ctx.program_id,
+    )?;
+
+    ctx.accounts.player.chests_opened = ctx.accounts.player.chests_opened.saturating_add(1);
+
+    Ok(())
+}
+
+//Creates the items of the chest in the next item accounts and returns the resources and LADA it
+//gives, which are given with give_chest_rewards so that a batch of chests is given at once
+//The item counter is the player's one, it's up to the caller to save it
+pub fn roll_chest_loot<'info>(
+    accounts: &ChestAccounts<'_, 'info>,
+    chest: &Item,
+    item_accounts: &mut Iter<AccountInfo<'info>>,
+    item_counter: &mut u64,
+    created_items: &mut Vec<Pubkey>,
+    slots: &[u8],
+    offset: &mut usize,
+) -> Result<([u64; 3], u64), ProgramError> {
+    let tier = match chest.item_type {
+        ItemType::Chest { tier } => tier,
+        _ => {
+            return Err(ErrorCode::ItemIsNotAChest.into());
+        }
+    };
+
+    let loot_table = accounts.game_config.get_loot_table(tier);
+
+    //Item level of chest gives range min level of the tier to chest level
+    let max_item_level = chest.level;
+    let min_item_level = loot_table.min_item_level.min(max_item_level);
+
+    let number_of_drops =
+        u8::random_within_range(slots, offset, loot_table.min_items, loot_table.max_items);
+    let total_weight = loot_table.weights.get_total_weight();
+
+    let mut resources_to_mint: [u64; 3] = [0, 0, 0];
+    let mut lada_to_transfer: u64 = 0;
+
+    for _ in 0..number_of_drops {
+        let drop = loot_table
+            .weights
+            .get_drop(u32::random_within_range(slots, offset, 1, total_weight));
+
+        match drop {
+            LootDrop::Resources => {
+                //Any of the 3 resources, scaled by the chest level
+                let resource_feature: ItemFeature =
+                    ItemFeature::get_random_within_range(slots, offset, 2, 4);
+                let resources = get_resources_for_feature(
+                    resource_feature,
+                    (loot_table.resources_per_level as u64)
+                        .checked_mul(chest.level as u64)
+                        .unwrap(),
+                );
+
+                for i in 0..resources_to_mint.len() {
+                    resources_to_mint[i] = resources_to_mint[i].checked_add(resources[i]).unwrap();
+                }
+            }
+            LootDrop::Lada => {
+                lada_to_transfer = lada_to_transfer
+                    .checked_add(
+                        (loot_table.lada_amount as u64)
+                            .checked_mul(DECIMALS_PRECISION)
+                            .unwrap(),
+                    )
+                    .unwrap();
+            }
+            LootDrop::SpellBook | LootDrop::Equipment | LootDrop::Consumable => {
+                let item_account = match item_accounts.next() {
+                    None => {
+                        return Err(ErrorCode::ChestItemAccountMissing.into());
+                    }
+                    Some(item_account) => item_account,
+                };
+
+                let mut item = create_item_account(
+                    item_account,
+                    &accounts.authority,
+                    &accounts.system_program,
+                    accounts.program_id,
+                    &accounts.player.key(),
+                    *item_counter,
+                )?;
+
+                *item_counter = item_counter.checked_add(1).unwrap();
+                created_items.push(item_account.key());
+
                 let item_level =
-                    u8::random_within_range(slots, &mut offset, min_item_level, max_item_level);
-
-                if u8::random_within_range(slots, &mut offset, 1, 4) == 1 {
-                    //This will generate a spell book (1 in 4 chances, between robe, staff, head and spell book)
-                    generate_new_spell_book(
-                        &mut item_to_create[i],
-                        game,
-                        player,
-                        item_level,
-                        slots,
-                        &mut offset,
-                    )?;
-                } else {
-                    generate_new_equipment(
-                        &mut item_to_create[i],
-                        game,
-                        player,
-                        item_level,
-                        None,
-                        slots,
-                        &mut offset,
-                    )?;
+                    u8::random_within_range(slots, offset, min_item_level, max_item_level);
+
+                match drop {
+                    LootDrop::SpellBook => {
+                        let rarity =
+                            get_item_rarity_with_floor(slots, offset, loot_table.rarity_floor);
+
+                        generate_new_spell_book(
+                            &mut item,
+                            accounts.game,
+                            accounts.player,
+                            item_level,
+                            Some(rarity),
+                            slots,
+                            offset,
+                        )?;
+                    }
+                    LootDrop::Consumable => {
+                        generate_new_consumable(
+                            &mut item,
+                            accounts.game,
+                            accounts.player,
+                            item_level,
+                            slots,
+                            offset,
+                        )?;
+                    }
+                    _ => {
+                        let rarity =
+                            get_item_rarity_with_floor(slots, offset, loot_table.rarity_floor);
+
+                        generate_new_equipment(
+                            &mut item,
+                            accounts.game,
+                            accounts.player,
+                            item_level,
+                            Some(rarity),
+                            slots,
+                            offset,
+                        )?;
+                    }
                 }
+
+                item.exit(accounts.program_id)?;
             }
         }
-        _ => {
-            return Err(ErrorCode::ItemIsNotAChest.into());
-        }
     }
 
-    zombify_account(
-        &mut ctx.accounts.chest,
-        ctx.accounts.authority.to_account_info(),
-        ctx.program_id,
+    Ok((resources_to_mint, lada_to_transfer))
+}
+
+pub fn give_chest_rewards(
+    accounts: &ChestAccounts,
+    turn_data: &mut TurnData,
+    resources_to_mint: [u64; 3],
+    lada_to_transfer: u64,
+) -> ProgramResult {
+    let seeds = &[b"game_signer".as_ref(), &[accounts.game.signer_bump]];
+    let signer = &[&seeds[..]];
+
+    mint_resources(
+        accounts.token_program.clone(),
+        accounts.resource_mint_accounts.clone(),
+        accounts.resource_token_accounts.clone(),
+        accounts.game_signer.clone(),
+        signer,
+        resources_to_mint,
     )?;
 
+    let lada_to_transfer = get_chest_lada(
+        lada_to_transfer,
+        turn_data,
+        accounts.game_config.chest_lada_per_turn,
+        accounts.game_lada_token_account.amount,
+    );
+
+    if lada_to_transfer > 0 {
+        turn_data.chest_lada_distributed = turn_data
+            .chest_lada_distributed
+            .checked_add(lada_to_transfer)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let cpi_accounts = Transfer {
+            from: accounts.game_lada_token_account.to_account_info(),
+            to: accounts.lada_token_account.clone(),
+            authority: accounts.game_signer.clone(),
+        };
+
+        let transfer_cpi = CpiContext::new(accounts.token_program.clone(), cpi_accounts);
+
+        token::transfer(transfer_cpi.with_signer(signer), lada_to_transfer)?;
+    }
+
     Ok(())
 }
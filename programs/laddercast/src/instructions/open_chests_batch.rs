@@ -0,0 +1,162 @@
+use anchor_lang::{prelude::*, solana_program::sysvar};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::account::*;
+use crate::error::ErrorCode;
+use crate::instructions::{give_chest_rewards, roll_chest_loot, ChestAccounts};
+use crate::utils::{check_chest_batch, update_inventory, close_item_account};
+
+#[derive(Accounts)]
+pub struct OpenChestsBatch<'info> {
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+
+    pub game: Box<Account<'info, Game>>,
+    #[account(seeds = [b"game_config", game.key().as_ref()], bump)]
+    pub game_config: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(mut, has_one = authority, has_one = game)]
+    pub player: Box<Account<'info, Player>>,
+    #[account(mut, seeds = [b"game_signer"], bump)]
+    pub game_signer: UncheckedAccount<'info>,
+
+    #[account(address = sysvar::slot_hashes::id())]
+    pub slots: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = resource_1_mint_account.to_account_info().key() == game.resource_1_mint_account)]
+    pub resource_1_mint_account: Box<Account<'info, Mint>>,
+    #[account(mut, constraint = resource_2_mint_account.to_account_info().key() == game.resource_2_mint_account)]
+    pub resource_2_mint_account: Box<Account<'info, Mint>>,
+    #[account(mut, constraint = resource_3_mint_account.to_account_info().key() == game.resource_3_mint_account)]
+    pub resource_3_mint_account: Box<Account<'info, Mint>>,
+
+    #[account(init_if_needed,
+    associated_token::mint = resource_1_mint_account,
+    associated_token::authority = authority,
+    payer = authority)]
+    pub resource_1_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(init_if_needed,
+    associated_token::mint = resource_2_mint_account,
+    associated_token::authority = authority,
+    payer = authority)]
+    pub resource_2_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(init_if_needed,
+    associated_token::mint = resource_3_mint_account,
+    associated_token::authority = authority,
+    payer = authority)]
+    pub resource_3_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = game_lada_token_account.key() == game.lada_token_account)]
+    pub game_lada_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = lada_token_account.mint == game.lada_mint_account)]
+    pub lada_token_account: Box<Account<'info, TokenAccount>>,
+    //Chest LADA comes out of the LADA of the current turn
+    #[account(mut, seeds = [b"turn_data", game.to_account_info().key().as_ref(), game.turn_info.turn.to_string().as_ref()], bump = game_turn_data.bump)]
+    pub game_turn_data: Box<Account<'info, TurnData>>,
+
+    #[account(mut, seeds = [b"inventory", player.key().as_ref()], bump)]
+    pub inventory: UncheckedAccount<'info>,
+    //The remaining accounts are the chests to open followed by the new item accounts, which need
+    //to be the next item PDAs of the player, used in order by the chests that drop items
+}
+
+pub fn open_chests_batch<'info>(
+    ctx: Context<'_, '_, '_, 'info, OpenChestsBatch<'info>>,
+    chest_count: u8,
+) -> ProgramResult {
+    check_chest_batch(chest_count, ctx.remaining_accounts.len())?;
+
+    let game_config =
+        GameConfig::load_or_default(&ctx.accounts.game_config, ctx.accounts.game.key())?;
+
+    let (chest_infos, item_infos) = ctx.remaining_accounts.split_at(chest_count as usize);
+
+    //Every chest rolls from the same slot hashes, the offset keeps advancing between them
+    let slots_ref = ctx.accounts.slots.data.borrow();
+    let slots = &**slots_ref;
+    let mut offset: usize = 1;
+
+    let chest_accounts = ChestAccounts {
+        program_id: ctx.program_id,
+        game: &ctx.accounts.game,
+        game_config: &game_config,
+        player: &ctx.accounts.player,
+        authority: ctx.accounts.authority.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        game_signer: ctx.accounts.game_signer.to_account_info(),
+        resource_mint_accounts: [
+            ctx.accounts.resource_1_mint_account.to_account_info(),
+            ctx.accounts.resource_2_mint_account.to_account_info(),
+            ctx.accounts.resource_3_mint_account.to_account_info(),
+        ],
+        resource_token_accounts: [
+            ctx.accounts.resource_1_token_account.to_account_info(),
+            ctx.accounts.resource_2_token_account.to_account_info(),
+            ctx.accounts.resource_3_token_account.to_account_info(),
+        ],
+        game_lada_token_account: &ctx.accounts.game_lada_token_account,
+        lada_token_account: ctx.accounts.lada_token_account.to_account_info(),
+    };
+
+    let mut resources_to_mint: [u64; 3] = [0, 0, 0];
+    let mut lada_to_transfer: u64 = 0;
+    let mut item_accounts = item_infos.iter();
+    let mut item_counter = ctx.accounts.player.item_counter;
+    let mut created_items: Vec<Pubkey> = vec![];
+
+    for chest_info in chest_infos.iter() {
+        //Loaded one at a time, a chest passed twice is already closed the second time
+        let chest: Account<Item> = Account::try_from(chest_info)?;
+
+        if chest.game != ctx.accounts.game.key() || chest.owner != ctx.accounts.player.key() {
+            return Err(ErrorCode::ChestNotOwned.into());
+        }
+
+        let (chest_resources, chest_lada) = roll_chest_loot(
+            &chest_accounts,
+            &chest,
+            &mut item_accounts,
+            &mut item_counter,
+            &mut created_items,
+            slots,
+            &mut offset,
+        )?;
+
+        for i in 0..resources_to_mint.len() {
+            resources_to_mint[i] = resources_to_mint[i].checked_add(chest_resources[i]).unwrap();
+        }
+        lada_to_transfer = lada_to_transfer.checked_add(chest_lada).unwrap();
+
+        close_item_account(&chest, ctx.accounts.authority.to_account_info())?;
+    }
+
+    give_chest_rewards(
+        &chest_accounts,
+        &mut ctx.accounts.game_turn_data,
+        resources_to_mint,
+        lada_to_transfer,
+    )?;
+
+    ctx.accounts.player.item_counter = item_counter;
+
+    let opened_chests: Vec<Pubkey> = chest_infos.iter().map(|chest_info| chest_info.key()).collect();
+
+    update_inventory(
+        &ctx.accounts.inventory,
+        &ctx.accounts.player.key(),
+        &created_items,
+        &opened_chests,
+        ctx.program_id,
+    )?;
+
+    ctx.accounts.player.chests_opened =
+        ctx.accounts.player.chests_opened.saturating_add(chest_count as u64);
+
+    Ok(())
+}
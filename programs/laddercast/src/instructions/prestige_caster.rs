@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::account::{Caster, Game, GameConfig, Player};
+use crate::error::ErrorCode;
+use crate::utils::{reset_caster_for_prestige, CASTER_VERSION};
+
+#[derive(Accounts)]
+pub struct PrestigeCaster<'info> {
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub game: Box<Account<'info, Game>>,
+    #[account(seeds = [b"game_config", game.key().as_ref()], bump)]
+    pub game_config: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = authority, has_one = game)]
+    pub player: Account<'info, Player>,
+
+    #[account(
+    mut,
+    constraint = caster.owner == player.key(),
+    constraint = caster.version == CASTER_VERSION @ ErrorCode::CasterNotMigrated
+    )]
+    pub caster: Box<Account<'info, Caster>>,
+}
+
+pub fn prestige_caster(ctx: Context<PrestigeCaster>) -> ProgramResult {
+    let game_config =
+        GameConfig::load_or_default(&ctx.accounts.game_config, ctx.accounts.game.key())?;
+    let caster = &mut ctx.accounts.caster;
+
+    if caster.level < game_config.experience_curve.level_cap {
+        return Err(ErrorCode::InvalidCasterPrestigeLevel.into());
+    }
+
+    if caster.turn_commit != None {
+        return Err(ErrorCode::InvalidCasterPrestigePendingTurn.into());
+    }
+
+    //Caster goes back to level 1, so items above that level can't stay equipped
+    if caster.modifiers.has_equipped_items() {
+        return Err(ErrorCode::InvalidCasterPrestigeEquipped.into());
+    }
+
+    reset_caster_for_prestige(caster);
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use anchor_lang::{prelude::*, solana_program::sysvar};
+use anchor_spl::token;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::account::*;
+use crate::error::ErrorCode;
+use crate::utils::{
+    burn_resources, get_reforge_cost, reforge_equipment, COST_IN_LADA_FOR_REFORGE,
+    DECIMALS_PRECISION,
+};
+
+#[derive(Accounts)]
+pub struct ReforgeItem<'info> {
+    pub token_program: Program<'info, Token>,
+
+    pub authority: Signer<'info>,
+
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(has_one = authority, has_one = game)]
+    pub player: Box<Account<'info, Player>>,
+
+    #[account(address = sysvar::slot_hashes::id())]
+    pub slots: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = resource_1_mint_account.to_account_info().key() == game.resource_1_mint_account)]
+    pub resource_1_mint_account: Box<Account<'info, Mint>>,
+    #[account(mut, constraint = resource_2_mint_account.to_account_info().key() == game.resource_2_mint_account)]
+    pub resource_2_mint_account: Box<Account<'info, Mint>>,
+    #[account(mut, constraint = resource_3_mint_account.to_account_info().key() == game.resource_3_mint_account)]
+    pub resource_3_mint_account: Box<Account<'info, Mint>>,
+
+    #[account(mut,
+    associated_token::mint = resource_1_mint_account,
+    associated_token::authority = authority)]
+    pub resource_1_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut,
+    associated_token::mint = resource_2_mint_account,
+    associated_token::authority = authority)]
+    pub resource_2_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut,
+    associated_token::mint = resource_3_mint_account,
+    associated_token::authority = authority)]
+    pub resource_3_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = lada_mint.to_account_info().key() == game.lada_mint_account)]
+    pub lada_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub lada_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+    mut,
+    constraint = item.game == game.key(),
+    constraint = item.owner == player.key(),
+    )]
+    pub item: Box<Account<'info, Item>>,
+}
+
+pub fn reforge_item(ctx: Context<ReforgeItem>, keep_best_value: bool) -> ProgramResult {
+    let item = &ctx.accounts.item;
+
+    //Bonuses of equipped items are used on redeem, so they can't change while equipped
+    if item.equipped_owner != None {
+        return Err(ErrorCode::ItemCantBeReforgedIfEquipped.into());
+    }
+
+    let resources_to_burn = match get_reforge_cost(&item.item_type, item.level, item.reforge_count) {
+        Some(resources) => resources,
+        None => return Err(ErrorCode::ItemCantBeReforged.into()),
+    };
+
+    let resource_balances = [
+        ctx.accounts.resource_1_token_account.amount,
+        ctx.accounts.resource_2_token_account.amount,
+        ctx.accounts.resource_3_token_account.amount,
+    ];
+
+    for i in 0..resources_to_burn.len() {
+        if resource_balances[i] < resources_to_burn[i] {
+            return Err(ErrorCode::PlayerIsPoor.into());
+        }
+    }
+
+    burn_resources(
+        ctx.accounts.token_program.to_account_info(),
+        [
+            ctx.accounts.resource_1_mint_account.to_account_info(),
+            ctx.accounts.resource_2_mint_account.to_account_info(),
+            ctx.accounts.resource_3_mint_account.to_account_info(),
+        ],
+        [
+            ctx.accounts.resource_1_token_account.to_account_info(),
+            ctx.accounts.resource_2_token_account.to_account_info(),
+            ctx.accounts.resource_3_token_account.to_account_info(),
+        ],
+        ctx.accounts.authority.to_account_info(),
+        &[],
+        resources_to_burn,
+    )?;
+
+    if keep_best_value {
+        //Decimal precision is 9
+        let amount: u64 = u64::from(COST_IN_LADA_FOR_REFORGE)
+            .checked_mul(DECIMALS_PRECISION)
+            .unwrap();
+
+        if ctx.accounts.lada_token_account.amount.checked_sub(amount) == None {
+            return Err(ErrorCode::PlayerIsPoor.into());
+        }
+
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info().clone(),
+                token::Burn {
+                    mint: ctx.accounts.lada_mint.to_account_info().clone(),
+                    to: ctx.accounts.lada_token_account.to_account_info().clone(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+    }
+
+    let slots_ref = ctx.accounts.slots.data.borrow();
+    let slots = &**slots_ref;
+    let mut offset: usize = 1;
+
+    reforge_equipment(&mut ctx.accounts.item, keep_best_value, slots, &mut offset)
+}
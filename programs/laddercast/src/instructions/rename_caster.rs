@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::account::{Caster, Game, Player};
+use crate::error::ErrorCode;
+use crate::utils::{get_caster_name_bytes, is_valid_caster_name, CASTER_VERSION};
+
+#[derive(Accounts)]
+pub struct RenameCaster<'info> {
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(mut, has_one = authority, has_one = game)]
+    pub player: Account<'info, Player>,
+
+    #[account(
+    mut,
+    constraint = caster.owner == player.key(),
+    constraint = caster.version == CASTER_VERSION @ ErrorCode::CasterNotMigrated
+    )]
+    pub caster: Box<Account<'info, Caster>>,
+}
+
+pub fn rename_caster(ctx: Context<RenameCaster>, name: String) -> ProgramResult {
+    if !is_valid_caster_name(&name) {
+        return Err(ErrorCode::InvalidCasterName.into());
+    }
+
+    ctx.accounts.caster.name = get_caster_name_bytes(&name);
+
+    Ok(())
+}
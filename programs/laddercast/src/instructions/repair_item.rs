@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::account::*;
+use crate::error::ErrorCode;
+use crate::utils::{burn_resources, get_repair_cost};
+
+#[derive(Accounts)]
+pub struct RepairItem<'info> {
+    pub token_program: Program<'info, Token>,
+
+    pub authority: Signer<'info>,
+
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(has_one = authority, has_one = game)]
+    pub player: Box<Account<'info, Player>>,
+
+    #[account(mut, constraint = resource_1_mint_account.to_account_info().key() == game.resource_1_mint_account)]
+    pub resource_1_mint_account: Box<Account<'info, Mint>>,
+    #[account(mut, constraint = resource_2_mint_account.to_account_info().key() == game.resource_2_mint_account)]
+    pub resource_2_mint_account: Box<Account<'info, Mint>>,
+    #[account(mut, constraint = resource_3_mint_account.to_account_info().key() == game.resource_3_mint_account)]
+    pub resource_3_mint_account: Box<Account<'info, Mint>>,
+
+    #[account(mut,
+    associated_token::mint = resource_1_mint_account,
+    associated_token::authority = authority)]
+    pub resource_1_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut,
+    associated_token::mint = resource_2_mint_account,
+    associated_token::authority = authority)]
+    pub resource_2_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut,
+    associated_token::mint = resource_3_mint_account,
+    associated_token::authority = authority)]
+    pub resource_3_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+    mut,
+    constraint = item.game == game.key(),
+    constraint = item.owner == player.key(),
+    )]
+    pub item: Box<Account<'info, Item>>,
+}
+
+pub fn repair_item(ctx: Context<RepairItem>) -> ProgramResult {
+    let resources_to_burn = match get_repair_cost(&ctx.accounts.item) {
+        Some(resources) => resources,
+        None => return Err(ErrorCode::ItemCantBeRepaired.into()),
+    };
+
+    let resource_balances = [
+        ctx.accounts.resource_1_token_account.amount,
+        ctx.accounts.resource_2_token_account.amount,
+        ctx.accounts.resource_3_token_account.amount,
+    ];
+
+    for i in 0..resources_to_burn.len() {
+        if resource_balances[i] < resources_to_burn[i] {
+            return Err(ErrorCode::PlayerIsPoor.into());
+        }
+    }
+
+    burn_resources(
+        ctx.accounts.token_program.to_account_info(),
+        [
+            ctx.accounts.resource_1_mint_account.to_account_info(),
+            ctx.accounts.resource_2_mint_account.to_account_info(),
+            ctx.accounts.resource_3_mint_account.to_account_info(),
+        ],
+        [
+            ctx.accounts.resource_1_token_account.to_account_info(),
+            ctx.accounts.resource_2_token_account.to_account_info(),
+            ctx.accounts.resource_3_token_account.to_account_info(),
+        ],
+        ctx.accounts.authority.to_account_info(),
+        &[],
+        resources_to_burn,
+    )?;
+
+    ctx.accounts.item.wear = 0;
+
+    Ok(())
+}
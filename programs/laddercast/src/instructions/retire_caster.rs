@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+
+use crate::account::{Caster, Game, GameConfig, Player};
+use crate::error::ErrorCode;
+use crate::utils::{close_action_queue, get_lada_refund_for_retired_caster, CASTER_VERSION};
+
+#[derive(Accounts)]
+pub struct RetireCaster<'info> {
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub game: Box<Account<'info, Game>>,
+    #[account(seeds = [b"game_config", game.key().as_ref()], bump)]
+    pub game_config: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"game_signer"], bump)]
+    pub game_signer: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = authority, has_one = game)]
+    pub player: Account<'info, Player>,
+
+    #[account(mut,
+    close = authority,
+    constraint = caster.owner == player.key(),
+    constraint = caster.version == CASTER_VERSION @ ErrorCode::CasterNotMigrated
+    )]
+    pub caster: Box<Account<'info, Caster>>,
+
+    //Might not exist if the caster never had planned turns
+    #[account(mut, seeds = [b"action_queue", caster.key().as_ref()], bump)]
+    pub action_queue: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = game_lada_token_account.key() == game.lada_token_account)]
+    pub game_lada_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut, constraint = lada_token_account.mint == game.lada_mint_account)]
+    pub lada_token_account: Account<'info, TokenAccount>,
+}
+
+pub fn retire_caster(ctx: Context<RetireCaster>) -> ProgramResult {
+    let game_config =
+        GameConfig::load_or_default(&ctx.accounts.game_config, ctx.accounts.game.key())?;
+    let caster = &ctx.accounts.caster;
+
+    if caster.turn_commit != None {
+        return Err(ErrorCode::InvalidCasterRetirePendingTurn.into());
+    }
+
+    if caster.modifiers.has_equipped_items() {
+        return Err(ErrorCode::InvalidCasterRetireEquipped.into());
+    }
+
+    //Refunds part of the LADA burned in init_caster, rent is given back by closing the account
+    let amount = get_lada_refund_for_retired_caster(
+        caster.level,
+        game_config.experience_curve.level_cap,
+    );
+
+    let cpi_accounts = Transfer {
+        from: ctx
+            .accounts
+            .game_lada_token_account
+            .to_account_info()
+            .clone(),
+        to: ctx.accounts.lada_token_account.to_account_info().clone(),
+        authority: ctx.accounts.game_signer.to_account_info().clone(),
+    };
+
+    let transfer_cpi = CpiContext::new(
+        ctx.accounts.token_program.to_account_info().clone(),
+        cpi_accounts,
+    );
+
+    let seeds = &[b"game_signer".as_ref(), &[ctx.accounts.game.signer_bump]];
+    let signer = &[&seeds[..]];
+
+    token::transfer(transfer_cpi.with_signer(signer), amount)?;
+
+    //Turns can't be planned for a caster that no longer exists
+    close_action_queue(
+        &mut ctx.accounts.player,
+        &ctx.accounts.action_queue.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        ctx.program_id,
+    )?;
+
+    Ok(())
+}
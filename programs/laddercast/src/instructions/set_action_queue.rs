@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::account::*;
+use crate::error::ErrorCode;
+use crate::utils::{set_action_queue_budget, QueuedTurnPlan, MAX_QUEUED_TURNS, CASTER_VERSION};
+
+#[derive(Accounts)]
+pub struct SetActionQueue<'info> {
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub game: Box<Account<'info, Game>>,
+    #[account(mut, has_one = authority, has_one = game)]
+    pub player: Box<Account<'info, Player>>,
+    #[account(
+    constraint = caster.owner == player.key(),
+    constraint = caster.version == CASTER_VERSION @ ErrorCode::CasterNotMigrated
+    )]
+    pub caster: Box<Account<'info, Caster>>,
+
+    #[account(init_if_needed,
+    seeds = [b"action_queue", caster.key().as_ref()],
+    bump,
+    payer = authority,
+    space = ActionQueue::SIZE)]
+    pub action_queue: Box<Account<'info, ActionQueue>>,
+
+    #[account(seeds = [b"game_signer"], bump)]
+    pub game_signer: UncheckedAccount<'info>,
+
+    #[account(constraint = resource_1_mint_account.to_account_info().key() == game.resource_1_mint_account)]
+    pub resource_1_mint_account: Box<Account<'info, Mint>>,
+    #[account(constraint = resource_2_mint_account.to_account_info().key() == game.resource_2_mint_account)]
+    pub resource_2_mint_account: Box<Account<'info, Mint>>,
+    #[account(constraint = resource_3_mint_account.to_account_info().key() == game.resource_3_mint_account)]
+    pub resource_3_mint_account: Box<Account<'info, Mint>>,
+
+    #[account(init_if_needed,
+    associated_token::mint = resource_1_mint_account,
+    associated_token::authority = authority,
+    payer = authority)]
+    pub resource_1_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(init_if_needed,
+    associated_token::mint = resource_2_mint_account,
+    associated_token::authority = authority,
+    payer = authority)]
+    pub resource_2_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(init_if_needed,
+    associated_token::mint = resource_3_mint_account,
+    associated_token::authority = authority,
+    payer = authority)]
+    pub resource_3_token_account: Box<Account<'info, TokenAccount>>,
+}
+
+//Replaces the caster's planned turns, the budget is the amount of each resource (in the
+//FIRE_INDEX, WATER_INDEX, EARTH_INDEX order) the keepers can burn on behalf of the player for
+//this queue, setting no plans and no budget cancels the queue
+pub fn set_action_queue(
+    ctx: Context<SetActionQueue>,
+    plans: Vec<QueuedTurnPlan>,
+    budget: [u64; 3],
+) -> ProgramResult {
+    if plans.len() > MAX_QUEUED_TURNS {
+        return Err(ErrorCode::ActionQueueTooLong.into());
+    }
+
+    if !plans.iter().all(|plan| plan.is_valid()) {
+        return Err(ErrorCode::InvalidQueuedTurnPlan.into());
+    }
+
+    let action_queue = &mut ctx.accounts.action_queue;
+
+    action_queue.bump = *ctx.bumps.get("action_queue").unwrap();
+    action_queue.caster = ctx.accounts.caster.key();
+    action_queue.plans = plans;
+
+    set_action_queue_budget(&mut ctx.accounts.player, action_queue, budget)?;
+
+    //A token account only has one delegate, so the game signer is approved for the budgets of all
+    //the player's queues
+    for (index, resource_token_account) in [
+        &ctx.accounts.resource_1_token_account,
+        &ctx.accounts.resource_2_token_account,
+        &ctx.accounts.resource_3_token_account,
+    ]
+        .iter()
+        .enumerate()
+    {
+        token::approve(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Approve {
+                    to: resource_token_account.to_account_info(),
+                    delegate: ctx.accounts.game_signer.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            ctx.accounts.player.queued_budget[index],
+        )?;
+    }
+
+    Ok(())
+}
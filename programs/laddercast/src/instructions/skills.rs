@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::account::{Caster, Game, Player};
+use crate::error::ErrorCode;
+use crate::utils::{
+    allocate_skill_points, reset_caster_skills, SkillType, COST_IN_LADA_FOR_RESPEC,
+    DECIMALS_PRECISION, CASTER_VERSION,
+};
+
+#[derive(Accounts)]
+pub struct AllocateSkill<'info> {
+    pub authority: Signer<'info>,
+
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(has_one = authority, has_one = game)]
+    pub player: Account<'info, Player>,
+
+    #[account(
+    mut,
+    constraint = caster.owner == player.key(),
+    constraint = caster.version == CASTER_VERSION @ ErrorCode::CasterNotMigrated
+    )]
+    pub caster: Box<Account<'info, Caster>>,
+}
+
+#[derive(Accounts)]
+pub struct RespecCaster<'info> {
+    pub token_program: Program<'info, Token>,
+
+    pub authority: Signer<'info>,
+
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(has_one = authority, has_one = game)]
+    pub player: Account<'info, Player>,
+
+    #[account(
+    mut,
+    constraint = caster.owner == player.key(),
+    constraint = caster.version == CASTER_VERSION @ ErrorCode::CasterNotMigrated
+    )]
+    pub caster: Box<Account<'info, Caster>>,
+
+    #[account(mut, constraint = lada_mint.to_account_info().key() == game.lada_mint_account)]
+    pub lada_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub lada_token_account: Account<'info, TokenAccount>,
+}
+
+pub fn allocate_skill(ctx: Context<AllocateSkill>, skill: SkillType, points: u8) -> ProgramResult {
+    let caster = &mut ctx.accounts.caster;
+
+    //Bonuses are computed on redeem, so they can't change between commit and redeem
+    if caster.turn_commit != None {
+        return Err(ErrorCode::InvalidSkillChangePendingTurn.into());
+    }
+
+    allocate_skill_points(caster, skill, points)
+}
+
+pub fn respec_caster(ctx: Context<RespecCaster>) -> ProgramResult {
+    if ctx.accounts.caster.turn_commit != None {
+        return Err(ErrorCode::InvalidSkillChangePendingTurn.into());
+    }
+
+    //Decimal precision is 9
+    let amount: u64 = u64::from(COST_IN_LADA_FOR_RESPEC)
+        .checked_mul(DECIMALS_PRECISION)
+        .unwrap();
+
+    if ctx.accounts.lada_token_account.amount.checked_sub(amount) == None {
+        return Err(ErrorCode::PlayerIsPoor.into());
+    }
+
+    //Same as caster creation, the fee is burned
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info().clone(),
+            token::Burn {
+                mint: ctx.accounts.lada_mint.to_account_info().clone(),
+                to: ctx.accounts.lada_token_account.to_account_info().clone(),
+                authority: ctx.accounts.authority.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    reset_caster_skills(&mut ctx.accounts.caster);
+
+    Ok(())
+}
@@ -7,7 +7,7 @@ use anchor_spl::token::{Mint, Token, TokenAccount, Transfer};
 
 use crate::account::*;
 use crate::error::ErrorCode;
-use crate::utils::ItemType;
+use crate::utils::{update_inventory, ItemType};
 use crate::{Tile, TileType};
 
 #[derive(Accounts)]
@@ -59,8 +59,15 @@ pub struct GiveItems<'info> {
     pub player: Account<'info, Player>,
     #[account(address = sysvar::slot_hashes::id())]
     pub slots: UncheckedAccount<'info>,
-    #[account(init, payer = authority, space = Item::SIZE)]
+    #[account(init,
+    seeds = [b"item", player.key().as_ref(), player.item_counter.to_string().as_ref()],
+    bump,
+    payer = authority,
+    space = Item::SIZE)]
     pub item: Box<Account<'info, Item>>,
+
+    #[account(mut, seeds = [b"inventory", player.key().as_ref()], bump)]
+    pub inventory: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
@@ -213,8 +220,27 @@ pub fn give_item(ctx: Context<GiveItems>, item_type: ItemType, level: u8) -> Pro
             item.level = level;
             item.item_type = item_type.clone();
         }
+        ItemType::Consumable { .. } => {
+            let item = &mut ctx.accounts.item;
+            item.game = ctx.accounts.game.key();
+            item.owner = player_acc.key();
+            item.equipped_owner = None;
+            item.level = level;
+            item.item_type = item_type.clone();
+        }
         _ => {}
     }
+
+    player_acc.item_counter = player_acc.item_counter.checked_add(1).unwrap();
+
+    update_inventory(
+        &ctx.accounts.inventory,
+        &ctx.accounts.player.key(),
+        &[ctx.accounts.item.key()],
+        &[],
+        ctx.program_id,
+    )?;
+
     Ok(())
     // }
     // #[cfg(not(feature = "debug"))]
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::account::{Caster, Game, Player};
+use crate::error::ErrorCode;
+use crate::utils::{check_caster_transfer, close_action_queue, CASTER_VERSION};
+
+#[derive(Accounts)]
+pub struct TransferCaster<'info> {
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(mut, has_one = authority, has_one = game)]
+    pub player: Account<'info, Player>,
+
+    #[account(constraint = new_player.game == game.key())]
+    pub new_player: Account<'info, Player>,
+
+    #[account(
+    mut,
+    constraint = caster.version == CASTER_VERSION @ ErrorCode::CasterNotMigrated
+    )]
+    pub caster: Box<Account<'info, Caster>>,
+
+    //Might not exist if the caster never had planned turns
+    #[account(mut, seeds = [b"action_queue", caster.key().as_ref()], bump)]
+    pub action_queue: UncheckedAccount<'info>,
+}
+
+pub fn transfer_caster(ctx: Context<TransferCaster>) -> ProgramResult {
+    check_caster_transfer(
+        &ctx.accounts.caster,
+        ctx.accounts.player.key(),
+        ctx.accounts.new_player.key(),
+    )?;
+
+    //Turns planned by the previous owner can't be executed with the resources of the new owner
+    close_action_queue(
+        &mut ctx.accounts.player,
+        &ctx.accounts.action_queue.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        ctx.program_id,
+    )?;
+
+    //Experience, level and position are kept as is, only the owner changes
+    ctx.accounts.caster.owner = ctx.accounts.new_player.key();
+
+    Ok(())
+}
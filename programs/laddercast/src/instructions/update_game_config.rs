@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use crate::account::*;
+use crate::error::ErrorCode;
+use crate::utils::{EmissionSchedule, ExperienceCurve, LootTable, SetBonuses};
+
+#[derive(Accounts)]
+pub struct UpdateGameConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(has_one = authority)]
+    pub game: Box<Account<'info, Game>>,
+
+    //Games created before the config existed get it created here
+    #[account(init_if_needed,
+    seeds = [b"game_config", game.key().as_ref()],
+    bump,
+    payer = authority,
+    space = GameConfig::SIZE
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+}
+
+//Only the settings that are provided are updated
+pub fn update_game_config(
+    ctx: Context<UpdateGameConfig>,
+    experience_curve: Option<ExperienceCurve>,
+    set_bonuses: Option<SetBonuses>,
+    loot_tables: Option<[LootTable; 4]>,
+    lada_distribution_weights: Option<[u16; 3]>,
+    emission_schedule: Option<EmissionSchedule>,
+    cancel_action_refund_percent: Option<u8>,
+    chest_lada_per_turn: Option<u64>,
+) -> ProgramResult {
+    let game_config = &mut ctx.accounts.game_config;
+
+    //Config was just created, start from the defaults the game was using without it
+    if game_config.game == Pubkey::default() {
+        ***game_config = GameConfig::new(
+            ctx.accounts.game.key(),
+            *ctx.bumps.get("game_config").unwrap(),
+        );
+    }
+
+    if let Some(experience_curve) = experience_curve {
+        if !experience_curve.is_valid() {
+            return Err(ErrorCode::InvalidExperienceCurve.into());
+        }
+
+        game_config.experience_curve = experience_curve;
+    }
+
+    if let Some(set_bonuses) = set_bonuses {
+        game_config.set_bonuses = set_bonuses;
+    }
+
+    if let Some(loot_tables) = loot_tables {
+        if !loot_tables.iter().all(|loot_table| loot_table.is_valid()) {
+            return Err(ErrorCode::InvalidLootTable.into());
+        }
+
+        game_config.loot_tables = loot_tables;
+    }
+
+    if let Some(lada_distribution_weights) = lada_distribution_weights {
+        if lada_distribution_weights == [0; 3] {
+            return Err(ErrorCode::InvalidLadaDistributionWeights.into());
+        }
+
+        game_config.lada_distribution_weights = lada_distribution_weights;
+    }
+
+    //Applies from the next crank, the current turn keeps its LADA
+    if let Some(emission_schedule) = emission_schedule {
+        if !emission_schedule.is_valid() {
+            return Err(ErrorCode::InvalidEmissionSchedule.into());
+        }
+
+        game_config.emission_schedule = Some(emission_schedule);
+    }
+
+    if let Some(cancel_action_refund_percent) = cancel_action_refund_percent {
+        if cancel_action_refund_percent > 100 {
+            return Err(ErrorCode::InvalidCancelActionRefundPercent.into());
+        }
+
+        game_config.cancel_action_refund_percent = Some(cancel_action_refund_percent);
+    }
+
+    if let Some(chest_lada_per_turn) = chest_lada_per_turn {
+        game_config.chest_lada_per_turn = chest_lada_per_turn;
+    }
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 
 use instructions::*;
 
-use crate::utils::{ItemFeature, ItemType};
+use crate::utils::{ActionType, ConsumableType, EmissionSchedule, ExperienceCurve, ItemFeature, ItemType, LootTable, QueuedTurnPlan, SetBonuses, SkillType};
 
 mod account;
 mod config;
@@ -24,8 +24,31 @@ pub mod laddercast {
         ctx: Context<InitGame>,
         turn_info: GameTurnInfo,
         merkle_root_nft: [u8; 32],
+        experience_curve: Option<ExperienceCurve>,
     ) -> ProgramResult {
-        init_game::init_game(ctx, turn_info, merkle_root_nft)
+        init_game::init_game(ctx, turn_info, merkle_root_nft, experience_curve)
+    }
+
+    pub fn update_game_config(
+        ctx: Context<UpdateGameConfig>,
+        experience_curve: Option<ExperienceCurve>,
+        set_bonuses: Option<SetBonuses>,
+        loot_tables: Option<[LootTable; 4]>,
+        lada_distribution_weights: Option<[u16; 3]>,
+        emission_schedule: Option<EmissionSchedule>,
+        cancel_action_refund_percent: Option<u8>,
+        chest_lada_per_turn: Option<u64>,
+    ) -> ProgramResult {
+        update_game_config::update_game_config(
+            ctx,
+            experience_curve,
+            set_bonuses,
+            loot_tables,
+            lada_distribution_weights,
+            emission_schedule,
+            cancel_action_refund_percent,
+            chest_lada_per_turn,
+        )
     }
 
     pub fn init_player(ctx: Context<InitPlayer>) -> ProgramResult {
@@ -36,6 +59,12 @@ pub mod laddercast {
         init_caster::init_caster(ctx)
     }
 
+    pub fn init_inventory<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitInventory<'info>>,
+    ) -> ProgramResult {
+        inventory::init_inventory(ctx)
+    }
+
     //********************************************
     //Turn based functions
     //********************************************
@@ -62,6 +91,27 @@ pub mod laddercast {
         caster_turn_redeem::caster_redeem_action(ctx)
     }
 
+    pub fn caster_cancel_action<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelAction<'info>>,
+        action: ActionType,
+    ) -> ProgramResult {
+        caster_cancel_action::caster_cancel_action(ctx, action)
+    }
+
+    pub fn set_action_queue(
+        ctx: Context<SetActionQueue>,
+        plans: Vec<QueuedTurnPlan>,
+        budget: [u64; 3],
+    ) -> ProgramResult {
+        set_action_queue::set_action_queue(ctx, plans, budget)
+    }
+
+    pub fn execute_queued_turn<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteQueuedTurn<'info>>,
+    ) -> ProgramResult {
+        execute_queued_turn::execute_queued_turn(ctx)
+    }
+
     pub fn crank(ctx: Context<Crank>) -> ProgramResult {
         crank::crank(ctx)
     }
@@ -78,10 +128,51 @@ pub mod laddercast {
         equipment::unequip_item(ctx)
     }
 
-    pub fn open_chest(ctx: Context<OpenChest>) -> ProgramResult {
+    pub fn set_loadout<'info>(
+        ctx: Context<'_, '_, '_, 'info, SetLoadout<'info>>,
+    ) -> ProgramResult {
+        equipment::set_loadout(ctx)
+    }
+
+    pub fn open_chest<'info>(
+        ctx: Context<'_, '_, '_, 'info, OpenChest<'info>>,
+    ) -> ProgramResult {
         open_chest::open_chest(ctx)
     }
 
+    pub fn open_chests_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, OpenChestsBatch<'info>>,
+        chest_count: u8,
+    ) -> ProgramResult {
+        open_chests_batch::open_chests_batch(ctx, chest_count)
+    }
+
+    pub fn salvage_item(ctx: Context<SalvageItem>) -> ProgramResult {
+        salvage_item::salvage_item(ctx)
+    }
+
+    pub fn reforge_item(ctx: Context<ReforgeItem>, keep_best_value: bool) -> ProgramResult {
+        reforge_item::reforge_item(ctx, keep_best_value)
+    }
+
+    pub fn repair_item(ctx: Context<RepairItem>) -> ProgramResult {
+        repair_item::repair_item(ctx)
+    }
+
+    pub fn stack_consumable(
+        ctx: Context<StackConsumable>,
+        consumable_type: ConsumableType,
+    ) -> ProgramResult {
+        consumables::stack_consumable(ctx, consumable_type)
+    }
+
+    pub fn use_consumable(
+        ctx: Context<UseConsumable>,
+        consumable_type: ConsumableType,
+    ) -> ProgramResult {
+        consumables::use_consumable(ctx, consumable_type)
+    }
+
     pub fn manual_resource_burn(
         ctx: Context<ManualResourceBurn>,
         resource_type: ItemFeature,
@@ -90,6 +181,34 @@ pub mod laddercast {
         manual_resource_burn::manual_resource_burn(ctx, resource_type, amount_to_burn)
     }
 
+    pub fn transfer_caster(ctx: Context<TransferCaster>) -> ProgramResult {
+        transfer_caster::transfer_caster(ctx)
+    }
+
+    pub fn retire_caster(ctx: Context<RetireCaster>) -> ProgramResult {
+        retire_caster::retire_caster(ctx)
+    }
+
+    pub fn prestige_caster(ctx: Context<PrestigeCaster>) -> ProgramResult {
+        prestige_caster::prestige_caster(ctx)
+    }
+
+    pub fn rename_caster(ctx: Context<RenameCaster>, name: String) -> ProgramResult {
+        rename_caster::rename_caster(ctx, name)
+    }
+
+    pub fn allocate_skill(
+        ctx: Context<AllocateSkill>,
+        skill: SkillType,
+        points: u8,
+    ) -> ProgramResult {
+        skills::allocate_skill(ctx, skill, points)
+    }
+
+    pub fn respec_caster(ctx: Context<RespecCaster>) -> ProgramResult {
+        skills::respec_caster(ctx)
+    }
+
     //********************************************
     //Functions to mint / burn into NFTs
     //********************************************
@@ -124,6 +243,17 @@ pub mod laddercast {
         update_merkle_root::update_merkle_root(ctx, merkle_root_nft)
     }
 
+    //********************************************
+    //Migrations of accounts created with an older layout
+    //********************************************
+    pub fn migrate_caster(ctx: Context<MigrateCaster>) -> ProgramResult {
+        migrate_caster::migrate_caster(ctx)
+    }
+
+    pub fn migrate_caster_metadata(ctx: Context<MigrateCasterMetadata>) -> ProgramResult {
+        migrate_caster::migrate_caster_metadata(ctx)
+    }
+
     //********************************************
     //Debug functions only for testing
     //********************************************
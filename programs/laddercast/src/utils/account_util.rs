@@ -0,0 +1,109 @@
+use anchor_lang::__private::CLOSED_ACCOUNT_DISCRIMINATOR;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::system_instruction;
+
+use crate::account::Item;
+use crate::error::ErrorCode;
+
+//Chests create a variable number of items, so their accounts are created here instead of with init
+//Like with init, the item account is the PDA ["item", player.key, item_index] of the player
+pub fn create_item_account<'info>(
+    item_account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    program_id: &Pubkey,
+    player: &Pubkey,
+    item_index: u64,
+) -> Result<Account<'info, Item>, ProgramError> {
+    let item_index = item_index.to_string();
+    let (item_key, item_bump) =
+        Pubkey::find_program_address(&[b"item", player.as_ref(), item_index.as_ref()], program_id);
+
+    if item_key != *item_account.key {
+        return Err(ErrorCode::InvalidItemAccount.into());
+    }
+
+    let seeds = &[b"item".as_ref(), player.as_ref(), item_index.as_ref(), &[item_bump]];
+    let signer = &[&seeds[..]];
+    let rent_exempt_lamports = Rent::get()?.minimum_balance(Item::SIZE);
+
+    if item_account.lamports() == 0 {
+        invoke_signed(
+            &system_instruction::create_account(
+                payer.key,
+                item_account.key,
+                rent_exempt_lamports,
+                Item::SIZE as u64,
+                program_id,
+            ),
+            &[payer.clone(), item_account.clone(), system_program.clone()],
+            signer,
+        )?;
+    } else {
+        //Anyone can send lamports to the PDA beforehand, create_account would fail on it
+        let missing_lamports = rent_exempt_lamports.saturating_sub(item_account.lamports());
+
+        if missing_lamports > 0 {
+            invoke(
+                &system_instruction::transfer(payer.key, item_account.key, missing_lamports),
+                &[payer.clone(), item_account.clone(), system_program.clone()],
+            )?;
+        }
+
+        invoke_signed(
+            &system_instruction::allocate(item_account.key, Item::SIZE as u64),
+            &[item_account.clone(), system_program.clone()],
+            signer,
+        )?;
+        invoke_signed(
+            &system_instruction::assign(item_account.key, program_id),
+            &[item_account.clone(), system_program.clone()],
+            signer,
+        )?;
+    }
+
+    Account::try_from_unchecked(item_account)
+}
+
+//Closes a consumed item passed in the remaining accounts (chest, crafting input, spell book...),
+//the items of the instruction accounts use the close constraint instead. Its data is zeroed and
+//marked with the closed account discriminator so it can't be used again, even if its rent is put
+//back
+pub fn close_item_account(item: &Account<Item>, destination: AccountInfo) -> ProgramResult {
+    close_account(&item.to_account_info(), &destination)
+}
+
+pub fn close_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    let destination_lamports = destination
+        .lamports()
+        .checked_add(account.lamports())
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    **destination.try_borrow_mut_lamports()? = destination_lamports;
+    **account.try_borrow_mut_lamports()? = 0;
+
+    let mut data = account.try_borrow_mut_data()?;
+    data.fill(0);
+
+    let discriminator_length = CLOSED_ACCOUNT_DISCRIMINATOR.len().min(data.len());
+    data[..discriminator_length]
+        .copy_from_slice(&CLOSED_ACCOUNT_DISCRIMINATOR[..discriminator_length]);
+
+    Ok(())
+}
+
+//Writes back the item created for a redeem, or closes it when nothing was generated in it, the
+//item is consumed so it can't be written back over the closed account afterwards
+pub fn finish_redeem_item(
+    item: Account<Item>,
+    is_item_used: bool,
+    destination: &AccountInfo,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    if is_item_used {
+        item.exit(program_id)
+    } else {
+        close_account(&item.to_account_info(), destination)
+    }
+}
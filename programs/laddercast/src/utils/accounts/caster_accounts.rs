@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use crate::utils::{CraftingSnapshot, SkillType, SpellSnapshot};
+
+/// Skill points spent by a caster in each branch of the talent tree
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy, Default)]
+pub struct SkillAllocation {
+    pub fire: u8,
+    pub water: u8,
+    pub earth: u8,
+    pub critical_chance: u8,
+    pub magic_find: u8,
+    pub crafting_luck: u8,
+}
+
+impl SkillAllocation {
+    pub const SIZE: usize = 6;
+
+    pub fn get_points(&self, skill: SkillType) -> u8 {
+        match skill {
+            SkillType::Fire => self.fire,
+            SkillType::Water => self.water,
+            SkillType::Earth => self.earth,
+            SkillType::CriticalChance => self.critical_chance,
+            SkillType::MagicFind => self.magic_find,
+            SkillType::CraftingLuck => self.crafting_luck,
+        }
+    }
+
+    pub fn add_points(&mut self, skill: SkillType, points: u8) {
+        let skill_points = match skill {
+            SkillType::Fire => &mut self.fire,
+            SkillType::Water => &mut self.water,
+            SkillType::Earth => &mut self.earth,
+            SkillType::CriticalChance => &mut self.critical_chance,
+            SkillType::MagicFind => &mut self.magic_find,
+            SkillType::CraftingLuck => &mut self.crafting_luck,
+        };
+
+        *skill_points = skill_points.saturating_add(points);
+    }
+
+    pub fn get_total_points(&self) -> u8 {
+        [
+            self.fire,
+            self.water,
+            self.earth,
+            self.critical_chance,
+            self.magic_find,
+            self.crafting_luck,
+        ]
+        .iter()
+        .fold(0u8, |total, points| total.saturating_add(*points))
+    }
+}
+
+/// Committed actions of version 1 casters, before the consumable effects
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Default, Copy)]
+pub struct LegacyCommittedActions {
+    pub loot: bool,
+    pub spell: Option<SpellSnapshot>,
+    pub mv: Option<[u8; 2]>,
+    pub crafting: Option<CraftingSnapshot>,
+    pub action_order: [u8; 4],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Default, Copy)]
+pub struct LegacyTurnCommit {
+    pub turn: u32,
+    pub resources_burned: [u64; 3],
+    pub actions: LegacyCommittedActions,
+}
+
+/// Modifiers of version 1 casters, before the amulet and ring slots
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Default, Copy)]
+pub struct LegacyModifiers {
+    pub tile_level: u8,
+    pub tile_column: u8,
+    pub head: Option<Pubkey>,
+    pub robe: Option<Pubkey>,
+    pub staff: Option<Pubkey>,
+    pub spell_book: Option<Pubkey>,
+}
+
+/// Layout of version 1 casters (and of the caster in their NFT metadata), only used to migrate them
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Default, Copy)]
+pub struct LegacyCaster {
+    pub version: u8,
+    pub level: u8,
+    pub experience: u64,
+    pub owner: Pubkey,
+    pub modifiers: LegacyModifiers,
+    pub turn_commit: Option<LegacyTurnCommit>,
+    pub prestige: u8,
+    pub name: [u8; 32],
+    pub skill_points: u8,
+    pub skills: SkillAllocation,
+}
@@ -0,0 +1,338 @@
+use anchor_lang::prelude::*;
+
+use crate::utils::{
+    ItemFeature, ItemRarity, LootDrop, EQUIPMENT_SET_SIZE,
+    EXPERIENCE_REQUIRED_PER_LEVEL, LADA_DISTRIBUTION_PER_TURN, MAX_CASTER_LEVEL, MAX_CHEST_ITEMS,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct ExperienceCurve {
+    /// Max level a caster can reach, 1..=MAX_CASTER_LEVEL since the map has 30 levels
+    pub level_cap: u8,
+    pub mode: ExperienceCurveMode,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub enum ExperienceCurveMode {
+    /// Experience needed to go from level n to n + 1 is at index n - 1
+    Table { experience_required_per_level: Vec<u64> },
+    /// Experience needed to go from level n to n + 1 is multiplier * n ^ exponent
+    Formula { multiplier: u64, exponent: u8 },
+}
+
+impl ExperienceCurve {
+    //Biggest variant is a full table
+    pub const SIZE: usize = 8 + 1 + 1 + 4 + 8 * MAX_CASTER_LEVEL as usize;
+
+    /// Total experience needed to go from level to level + 1
+    pub fn get_experience_required(&self, level: u8) -> u64 {
+        match &self.mode {
+            ExperienceCurveMode::Table {
+                experience_required_per_level,
+            } => match experience_required_per_level.get(level.saturating_sub(1) as usize) {
+                Some(experience_required) => *experience_required,
+                None => u64::MAX,
+            },
+            ExperienceCurveMode::Formula {
+                multiplier,
+                exponent,
+            } => multiplier.saturating_mul((level as u64).saturating_pow(*exponent as u32)),
+        }
+    }
+
+    /// Total experience a caster has when it just reached the level
+    pub fn get_min_experience_for_level(&self, level: u8) -> u64 {
+        if level <= 1 {
+            0
+        } else {
+            self.get_experience_required(level - 1)
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        if self.level_cap == 0 || self.level_cap > MAX_CASTER_LEVEL {
+            return false;
+        }
+
+        match &self.mode {
+            ExperienceCurveMode::Table {
+                experience_required_per_level,
+            } => {
+                //Every level below the cap needs an entry, and the curve can't go down
+                experience_required_per_level.len() >= (self.level_cap - 1) as usize
+                    && experience_required_per_level.len() <= MAX_CASTER_LEVEL as usize
+                    && experience_required_per_level
+                    .windows(2)
+                    .all(|window| window[0] <= window[1])
+            }
+            //An exponent of 0 would need the same experience for every level
+            ExperienceCurveMode::Formula {
+                multiplier,
+                exponent,
+            } => *multiplier > 0 && *exponent > 0,
+        }
+    }
+}
+
+impl Default for ExperienceCurve {
+    fn default() -> Self {
+        Self {
+            level_cap: MAX_CASTER_LEVEL,
+            mode: ExperienceCurveMode::Table {
+                experience_required_per_level: EXPERIENCE_REQUIRED_PER_LEVEL.to_vec(),
+            },
+        }
+    }
+}
+
+/// Extra % given to the equipment bonuses when at least 3 equipped pieces match
+/// All zeros (configs created before set bonuses existed) means no set bonus
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy)]
+pub struct SetBonuses {
+    /// A set of pieces have the same feature
+    pub same_feature_percent: u16,
+    /// A set of pieces have the same rarity, indexed by rarity (common to legendary)
+    pub same_rarity_percent: [u16; 4],
+}
+
+impl SetBonuses {
+    pub const SIZE: usize = 2 + 2 * 4;
+
+    /// Pieces are the feature and rarity of each contributing equipment, a set is 3 matching pieces
+    pub fn get_bonus_percent(&self, pieces: &[(ItemFeature, ItemRarity)]) -> u16 {
+        let mut bonus_percent: u16 = 0;
+
+        //With 5 slots there can't be two different sets of the same kind
+        if pieces.iter().any(|piece| {
+            pieces.iter().filter(|other| other.0 == piece.0).count() >= EQUIPMENT_SET_SIZE
+        }) {
+            bonus_percent = bonus_percent.saturating_add(self.same_feature_percent);
+        }
+
+        if let Some(piece) = pieces.iter().find(|piece| {
+            pieces.iter().filter(|other| other.1 == piece.1).count() >= EQUIPMENT_SET_SIZE
+        }) {
+            bonus_percent = bonus_percent.saturating_add(self.same_rarity_percent[piece.1 as usize]);
+        }
+
+        bonus_percent
+    }
+}
+
+impl Default for SetBonuses {
+    fn default() -> Self {
+        Self {
+            same_feature_percent: 20,
+            same_rarity_percent: [5, 10, 15, 25],
+        }
+    }
+}
+
+/// Relative chances of each drop of a loot table, a drop with a weight of 0 never happens
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy, Default)]
+pub struct LootWeights {
+    pub spell_book: u16,
+    pub equipment: u16,
+    pub consumable: u16,
+    pub resources: u16,
+    pub lada: u16,
+}
+
+impl LootWeights {
+    pub const SIZE: usize = 2 * 5;
+
+    fn get_drops(&self) -> [(LootDrop, u16); 5] {
+        [
+            (LootDrop::SpellBook, self.spell_book),
+            (LootDrop::Equipment, self.equipment),
+            (LootDrop::Consumable, self.consumable),
+            (LootDrop::Resources, self.resources),
+            (LootDrop::Lada, self.lada),
+        ]
+    }
+
+    pub fn get_total_weight(&self) -> u32 {
+        self.get_drops().iter().map(|(_, weight)| *weight as u32).sum()
+    }
+
+    /// Roll is between 1 and the total weight
+    pub fn get_drop(&self, roll: u32) -> LootDrop {
+        let mut cumulated_weight: u32 = 0;
+
+        for (drop, weight) in self.get_drops().iter() {
+            cumulated_weight += *weight as u32;
+
+            if roll <= cumulated_weight {
+                return *drop;
+            }
+        }
+
+        //Roll is above the total weight, only happens if it was out of range
+        LootDrop::Equipment
+    }
+}
+
+/// Content of the chests of one tier
+/// All zeros (configs created before loot tables existed) means the default table of the tier
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy, Default)]
+pub struct LootTable {
+    /// Items go from this level to the chest level
+    pub min_item_level: u8,
+    pub min_items: u8,
+    pub max_items: u8,
+    pub weights: LootWeights,
+    /// Spell books and equipment are at least this rare
+    pub rarity_floor: ItemRarity,
+    /// Resources of a resources drop, multiplied by the chest level
+    pub resources_per_level: u16,
+    /// LADA of a LADA drop, without decimals
+    pub lada_amount: u16,
+}
+
+impl LootTable {
+    pub const SIZE: usize = 1 + 1 + 1 + LootWeights::SIZE + 1 + 2 + 2;
+
+    pub fn is_empty(&self) -> bool {
+        self.max_items == 0 && self.weights.get_total_weight() == 0
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.min_item_level > 0
+            && self.min_items <= self.max_items
+            && self.max_items > 0
+            && self.max_items <= MAX_CHEST_ITEMS
+            && self.weights.get_total_weight() > 0
+    }
+
+    /// Tier is 1 to 4
+    pub fn get_default_for_tier(tier: u8) -> Self {
+        //Same as the chests from before loot tables, 3 items with 1 in 4 being a spell book
+        let min_item_level = match tier {
+            2 => 6,
+            3 => 11,
+            4 => 16,
+            _ => 1,
+        };
+
+        Self {
+            min_item_level,
+            min_items: 3,
+            max_items: 3,
+            weights: LootWeights {
+                spell_book: 1,
+                equipment: 3,
+                consumable: 0,
+                resources: 0,
+                lada: 0,
+            },
+            rarity_floor: ItemRarity::Common,
+            resources_per_level: 0,
+            lada_amount: 0,
+        }
+    }
+}
+
+pub fn get_default_loot_tables() -> [LootTable; 4] {
+    [
+        LootTable::get_default_for_tier(1),
+        LootTable::get_default_for_tier(2),
+        LootTable::get_default_for_tier(3),
+        LootTable::get_default_for_tier(4),
+    ]
+}
+
+/// LADA given to the casters of each turn, the amount of a turn is computed when it's cranked
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy)]
+pub enum EmissionSchedule {
+    /// Same amount every turn
+    Flat { amount_per_turn: u64 },
+    /// Amount is halved every halving_period turns from start_turn
+    StepHalving {
+        initial_amount: u64,
+        start_turn: u32,
+        halving_period: u32,
+    },
+    /// Amount goes down linearly from initial_amount at start_turn to 0 after decay_turns turns
+    LinearDecay {
+        initial_amount: u64,
+        start_turn: u32,
+        decay_turns: u32,
+    },
+    /// Budget is split evenly between the season_turns turns from start_turn, nothing after them
+    SeasonBudget {
+        budget: u64,
+        start_turn: u32,
+        season_turns: u32,
+    },
+}
+
+impl EmissionSchedule {
+    pub const SIZE: usize = 1 + 8 + 4 + 4;
+
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            EmissionSchedule::Flat { .. } => true,
+            EmissionSchedule::StepHalving { halving_period, .. } => halving_period > 0,
+            EmissionSchedule::LinearDecay { decay_turns, .. } => decay_turns > 0,
+            EmissionSchedule::SeasonBudget { season_turns, .. } => season_turns > 0,
+        }
+    }
+
+    /// Turns before start_turn get the starting amount, a season budget gives nothing before its
+    /// season starts
+    pub fn get_lada_for_turn(&self, turn: u32) -> u64 {
+        match *self {
+            EmissionSchedule::Flat { amount_per_turn } => amount_per_turn,
+            EmissionSchedule::StepHalving {
+                initial_amount,
+                start_turn,
+                halving_period,
+            } => {
+                let halvings = turn.saturating_sub(start_turn) / halving_period.max(1);
+
+                initial_amount.checked_shr(halvings).unwrap_or(0)
+            }
+            EmissionSchedule::LinearDecay {
+                initial_amount,
+                start_turn,
+                decay_turns,
+            } => {
+                let turns_left = decay_turns.saturating_sub(turn.saturating_sub(start_turn));
+
+                (initial_amount as u128 * turns_left as u128 / decay_turns.max(1) as u128) as u64
+            }
+            EmissionSchedule::SeasonBudget {
+                budget,
+                start_turn,
+                season_turns,
+            } => {
+                if turn < start_turn {
+                    return 0;
+                }
+
+                let season_turn = turn - start_turn;
+
+                if season_turn >= season_turns {
+                    return 0;
+                }
+
+                //The rounding remainder goes to the last turn so the whole budget is given
+                let amount_per_turn = budget / season_turns as u64;
+
+                if season_turn == season_turns - 1 {
+                    amount_per_turn + budget % season_turns as u64
+                } else {
+                    amount_per_turn
+                }
+            }
+        }
+    }
+}
+
+impl Default for EmissionSchedule {
+    fn default() -> Self {
+        EmissionSchedule::Flat {
+            amount_per_turn: LADA_DISTRIBUTION_PER_TURN,
+        }
+    }
+}
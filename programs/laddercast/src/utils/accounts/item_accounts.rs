@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+use crate::utils::ItemFeature;
+
+/// Extra bonus rolled on epic and legendary equipment, always a different feature than the main one
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy)]
+pub struct SecondaryStat {
+    pub feature: ItemFeature,
+    /// Scaled like the main value for that feature, but reduced by SECONDARY_STAT_VALUE_PERCENT
+    pub value: u16,
+}
+
+impl SecondaryStat {
+    pub const SIZE: usize = 1 + 2;
+}
@@ -1,5 +1,11 @@
+pub use caster_accounts::*;
+pub use config_accounts::*;
+pub use item_accounts::*;
 pub use metaplex_accounts::*;
 pub use turn_accounts::*;
 
+pub mod caster_accounts;
+pub mod config_accounts;
+pub mod item_accounts;
 pub mod turn_accounts;
 pub mod metaplex_accounts;
\ No newline at end of file
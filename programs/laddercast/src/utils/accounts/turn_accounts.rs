@@ -2,7 +2,8 @@ use std::mem::size_of;
 
 use anchor_lang::prelude::*;
 
-use crate::utils::ItemRarity;
+use crate::error::ErrorCode;
+use crate::utils::{ActionType, ConsumableType, ItemRarity};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Default, Copy)]
 pub struct TurnCommit {
@@ -22,11 +23,20 @@ pub struct CommittedActions {
     pub mv: Option<[u8; 2]>,
     pub crafting: Option<CraftingSnapshot>,
     pub action_order: [u8; 4],
+    pub consumables: ConsumableEffects,
 }
 
 impl CommittedActions {
-    pub const SIZE: usize =
-        8 + 1 + 1 + SpellSnapshot::SIZE + 1 + 1 * 2 + 1 + CraftingSnapshot::SIZE + 1 * 4;
+    pub const SIZE: usize = 8
+        + 1
+        + 1
+        + SpellSnapshot::SIZE
+        + 1
+        + 1 * 2
+        + 1
+        + CraftingSnapshot::SIZE
+        + 1 * 4
+        + ConsumableEffects::SIZE;
 
     fn get_highest_value(&self) -> u8 {
         *self
@@ -44,6 +54,119 @@ impl CommittedActions {
         }
         self.action_order[index as usize] = self.get_highest_value() + 1;
     }
+
+    /// Removes an action from the order, actions committed after it are moved back by 1
+    pub fn remove_action_order(&mut self, index: u8) -> ProgramResult {
+        if (index as usize) >= self.action_order.len() {
+            return Err(ErrorCode::InvalidActionOrderIndex.into());
+        }
+
+        let removed_order = self.action_order[index as usize];
+
+        if removed_order == 0 {
+            return Ok(());
+        }
+
+        self.action_order[index as usize] = 0;
+
+        for order in self.action_order.iter_mut() {
+            if *order > removed_order {
+                *order -= 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn is_committed_after(&self, index: u8, other_index: u8) -> bool {
+        self.action_order[other_index as usize] > self.action_order[index as usize]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.action_order.iter().all(|order| *order == 0)
+    }
+
+    /// Level and column an action is done on, which is the move destination if the caster moved
+    /// before it (or before committing it), the current tile otherwise
+    pub fn get_action_tile(&self, index: u8, current_tile: (u8, u8)) -> (u8, u8) {
+        let move_index = ActionType::Move.get_action_order_index();
+
+        match self.mv {
+            Some([level, column])
+                if self.action_order[index as usize] == 0
+                    || self.is_committed_after(move_index, index) =>
+            {
+                (level, column)
+            }
+            _ => current_tile,
+        }
+    }
+}
+
+/// Consumables used during the turn, their effects are applied when the turn is redeemed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Default, Copy)]
+pub struct ConsumableEffects {
+    pub guaranteed_critical: bool,
+    pub free_move: bool,
+    pub double_chest_chance: bool,
+}
+
+impl ConsumableEffects {
+    pub const SIZE: usize = 1 + 1 + 1;
+
+    pub fn is_active(&self, consumable_type: ConsumableType) -> bool {
+        match consumable_type {
+            ConsumableType::CritPotion => self.guaranteed_critical,
+            ConsumableType::MoveScroll => self.free_move,
+            ConsumableType::ChestCharm => self.double_chest_chance,
+        }
+    }
+
+    pub fn activate(&mut self, consumable_type: ConsumableType) {
+        match consumable_type {
+            ConsumableType::CritPotion => self.guaranteed_critical = true,
+            ConsumableType::MoveScroll => self.free_move = true,
+            ConsumableType::ChestCharm => self.double_chest_chance = true,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.guaranteed_critical && !self.free_move && !self.double_chest_chance
+    }
+}
+
+/// Actions a keeper commits on behalf of the player for one turn of the action queue
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Default, Copy)]
+pub struct QueuedTurnPlan {
+    pub spell: bool,
+    /// Level, column
+    pub mv: Option<[u8; 2]>,
+    pub loot: bool,
+    /// Items burned for crafting
+    pub crafting: Option<[Pubkey; 3]>,
+}
+
+impl QueuedTurnPlan {
+    pub const SIZE: usize = 8 + 1 + 1 + 1 * 2 + 1 + 1 + 32 * 3;
+
+    /// Plans need at least one action, a move on the map and 3 different crafting items
+    pub fn is_valid(&self) -> bool {
+        let is_valid_move = match self.mv {
+            Some([level, column]) => level < 30 && column < 3,
+            None => true,
+        };
+
+        let is_valid_crafting = match self.crafting {
+            Some([item_1, item_2, item_3]) => {
+                item_1 != item_2 && item_1 != item_3 && item_2 != item_3
+            }
+            None => true,
+        };
+
+        (self.spell || self.mv != None || self.loot || self.crafting != None)
+            && is_valid_move
+            && is_valid_crafting
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy)]
@@ -81,8 +204,32 @@ pub struct Modifiers {
     pub staff: Option<Pubkey>,
     /// Item
     pub spell_book: Option<Pubkey>,
+    /// Item
+    pub amulet: Option<Pubkey>,
+    /// Item
+    pub ring: Option<Pubkey>,
 }
 
 impl Modifiers {
-    pub const SIZE: usize = 8 + 1 + 1 + (1 + 32) * 4;
+    pub const SIZE: usize = 8 + 1 + 1 + (1 + 32) * 6;
+
+    pub fn has_equipped_items(&self) -> bool {
+        [self.head, self.robe, self.staff, self.spell_book, self.amulet, self.ring]
+            .iter()
+            .any(|item_pub| *item_pub != None)
+    }
+
+    //Equipped items in the head, robe, staff, amulet, ring, spell book order
+    pub fn get_loadout(&self) -> [Option<Pubkey>; 6] {
+        [self.head, self.robe, self.staff, self.amulet, self.ring, self.spell_book]
+    }
+
+    pub fn set_loadout(&mut self, loadout: [Option<Pubkey>; 6]) {
+        self.head = loadout[0];
+        self.robe = loadout[1];
+        self.staff = loadout[2];
+        self.amulet = loadout[3];
+        self.ring = loadout[4];
+        self.spell_book = loadout[5];
+    }
 }
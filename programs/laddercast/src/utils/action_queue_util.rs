@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::account::{ActionQueue, Player};
+use crate::error::ErrorCode;
+use crate::utils::close_account;
+
+//Replaces the budget of an action queue, the player's total follows so it can be approved
+pub fn set_action_queue_budget(
+    player: &mut Player,
+    action_queue: &mut ActionQueue,
+    budget: [u64; 3],
+) -> ProgramResult {
+    for i in 0..budget.len() {
+        player.queued_budget[i] = player.queued_budget[i]
+            .saturating_sub(action_queue.budget[i])
+            .checked_add(budget[i])
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+
+    action_queue.budget = budget;
+
+    Ok(())
+}
+
+//Resources burned by a keeper come out of the queue budget, the token program already lowers the
+//approved amount by what the game signer burned
+pub fn spend_action_queue_budget(
+    player: &mut Player,
+    action_queue: &mut ActionQueue,
+    resources_burned: [u64; 3],
+) -> ProgramResult {
+    for i in 0..resources_burned.len() {
+        if resources_burned[i] > action_queue.budget[i] {
+            return Err(ErrorCode::ActionQueueBudgetExceeded.into());
+        }
+    }
+
+    for i in 0..resources_burned.len() {
+        action_queue.budget[i] -= resources_burned[i];
+        player.queued_budget[i] = player.queued_budget[i].saturating_sub(resources_burned[i]);
+    }
+
+    Ok(())
+}
+
+//Planned turns can't outlive their caster's owner, the queue's budget stops counting in the
+//player's total and its rent goes to the destination
+pub fn close_action_queue(
+    player: &mut Player,
+    action_queue_account: &AccountInfo,
+    destination: &AccountInfo,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    //Might not exist if the caster never had planned turns
+    if action_queue_account.owner != program_id {
+        return Ok(());
+    }
+
+    let mut action_queue: Account<ActionQueue> = Account::try_from(action_queue_account)?;
+
+    //The game signer stays approved for the old total until the next queue is set, but it
+    //can only burn within the budgets of the remaining queues
+    set_action_queue_budget(player, &mut action_queue, [0; 3])?;
+
+    close_account(action_queue_account, destination)
+}
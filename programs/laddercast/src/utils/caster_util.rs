@@ -1,5 +1,15 @@
-use crate::account::Caster;
-use crate::utils::{ItemRarity, RandomValue};
+use anchor_lang::prelude::*;
+use solana_maths::{Decimal, TryAdd, TryDiv, TryMul};
+
+use crate::account::{Caster, CasterStats};
+use crate::error::ErrorCode;
+use crate::utils::{
+    CommittedActions, ConsumableEffects, ExperienceCurve, ItemRarity, LegacyCaster,
+    LegacyTurnCommit, Modifiers, RandomValue, SkillAllocation, SkillType, TurnCommit,
+    CASTER_NAME_ALLOWED_SPECIAL_CHARACTERS, CASTER_VERSION,
+    COST_IN_LADA_FOR_CASTER, DECIMALS_PRECISION, MAX_CASTER_NAME_LENGTH,
+    MAX_REFUND_PERCENT_FOR_RETIRED_CASTER,
+};
 
 pub const EXPERIENCE_REQUIRED_PER_LEVEL: [u64; 30] = [
     1508, 6001, 14956, 29851,
@@ -17,23 +27,103 @@ pub fn give_exp_to_caster_resources_burned(
     fire_burned: Option<u64>,
     earth_burned: Option<u64>,
     water_burned: Option<u64>,
+    experience_curve: &ExperienceCurve,
 ) {
     //Give exp to user based on burned resources
     caster.experience +=
         fire_burned.unwrap_or(0) + earth_burned.unwrap_or(0) + water_burned.unwrap_or(0);
 
+    level_up_caster(caster, experience_curve);
+}
+
+pub fn give_exp_to_caster_spell(caster: &mut Caster, value: u64, experience_curve: &ExperienceCurve) {
+    caster.experience += value;
+
+    level_up_caster(caster, experience_curve);
+}
 
-    //Since 0 based, we don't add +1 to level
-    while caster.level < 30 && caster.experience >= EXPERIENCE_REQUIRED_PER_LEVEL[(caster.level - 1) as usize] {
+fn level_up_caster(caster: &mut Caster, experience_curve: &ExperienceCurve) {
+    while caster.level < experience_curve.level_cap
+        && caster.experience >= experience_curve.get_experience_required(caster.level)
+    {
         caster.level += 1;
+        //Every level gained can be spent in the talent tree
+        caster.skill_points = caster.skill_points.saturating_add(1);
     }
 }
 
-pub fn give_exp_to_caster_spell(caster: &mut Caster, value: u64) {
-    caster.experience += value;
+pub fn reset_caster_for_prestige(caster: &mut Caster) {
+    //Caster starts over from the bottom of the ladder, but keeps a permanent bonus
+    caster.level = 1;
+    caster.experience = 0;
+    caster.modifiers.tile_level = 0;
+    caster.prestige = caster.prestige.saturating_add(1);
+    //Skill points are earned again while climbing back up
+    caster.skill_points = 0;
+    caster.skills = SkillAllocation::default();
+}
 
-    while caster.level < 30 && caster.experience >= EXPERIENCE_REQUIRED_PER_LEVEL[(caster.level - 1) as usize] {
-        caster.level += 1;
+//To prevent infinite number of nft metadata, minted casters get the min experience of their level
+pub fn reset_caster_experience_for_mint(caster: &mut Caster, experience_curve: &ExperienceCurve) {
+    caster.level = caster.level.min(experience_curve.level_cap);
+    caster.experience = experience_curve.get_min_experience_for_level(caster.level);
+}
+
+pub fn allocate_skill_points(caster: &mut Caster, skill: SkillType, points: u8) -> ProgramResult {
+    if points == 0 || caster.skill_points < points {
+        return Err(ErrorCode::NotEnoughSkillPoints.into());
+    }
+
+    caster.skill_points -= points;
+    caster.skills.add_points(skill, points);
+
+    Ok(())
+}
+
+pub fn reset_caster_skills(caster: &mut Caster) {
+    caster.skill_points = caster
+        .skill_points
+        .saturating_add(caster.skills.get_total_points());
+    caster.skills = SkillAllocation::default();
+}
+
+//Version 1 casters get the amulet and ring slots and the consumable effects in a single step
+pub fn migrate_legacy_caster(legacy_caster: &LegacyCaster) -> Caster {
+    Caster {
+        version: CASTER_VERSION,
+        level: legacy_caster.level,
+        experience: legacy_caster.experience,
+        owner: legacy_caster.owner,
+        modifiers: Modifiers {
+            tile_level: legacy_caster.modifiers.tile_level,
+            tile_column: legacy_caster.modifiers.tile_column,
+            head: legacy_caster.modifiers.head,
+            robe: legacy_caster.modifiers.robe,
+            staff: legacy_caster.modifiers.staff,
+            spell_book: legacy_caster.modifiers.spell_book,
+            amulet: None,
+            ring: None,
+        },
+        turn_commit: legacy_caster.turn_commit.map(|turn_commit| migrate_legacy_turn_commit(&turn_commit)),
+        prestige: legacy_caster.prestige,
+        name: legacy_caster.name,
+        skill_points: legacy_caster.skill_points,
+        skills: legacy_caster.skills,
+    }
+}
+
+fn migrate_legacy_turn_commit(legacy_turn_commit: &LegacyTurnCommit) -> TurnCommit {
+    TurnCommit {
+        turn: legacy_turn_commit.turn,
+        resources_burned: legacy_turn_commit.resources_burned,
+        actions: CommittedActions {
+            loot: legacy_turn_commit.actions.loot,
+            spell: legacy_turn_commit.actions.spell,
+            mv: legacy_turn_commit.actions.mv,
+            crafting: legacy_turn_commit.actions.crafting,
+            action_order: legacy_turn_commit.actions.action_order,
+            consumables: ConsumableEffects::default(),
+        },
     }
 }
 
@@ -47,4 +137,119 @@ pub fn is_spell_successful(slots: &[u8], offset: &mut usize, spell_book_rarity:
     };
 
     u8::random_within_range(slots, offset, 1, max_range) == 1
-}
\ No newline at end of file
+}
+
+pub fn get_lada_refund_for_retired_caster(level: u8, level_cap: u8) -> u64 {
+    //Refund is a share of the LADA burned at creation, the higher the level the bigger the share
+    let level_cap = level_cap.max(1);
+    let level = level.min(level_cap) as u64;
+
+    u64::from(COST_IN_LADA_FOR_CASTER)
+        .checked_mul(DECIMALS_PRECISION)
+        .unwrap()
+        .checked_mul(MAX_REFUND_PERCENT_FOR_RETIRED_CASTER)
+        .unwrap()
+        .checked_mul(level)
+        .unwrap()
+        / (100 * level_cap as u64)
+}
+
+//Share of the turn's LADA earned with the resources burned by the caster, in the FIRE_INDEX,
+//WATER_INDEX, EARTH_INDEX order, each element gives its weighted part of the LADA pro rata
+//Computed in fixed point and rounded down, so the casters of a turn never get more than its LADA
+pub fn get_lada_reward_for_resources_burned(
+    resources_burned: [u64; 3],
+    total_resources_burned: [u64; 3],
+    weights: [u16; 3],
+    lada_per_turn: u64,
+) -> Result<u64, ProgramError> {
+    let total_weight: u64 = weights.iter().map(|weight| *weight as u64).sum();
+
+    if total_weight == 0 {
+        return Ok(0);
+    }
+
+    let mut lada_reward = Decimal::zero();
+
+    for i in 0..resources_burned.len() {
+        if total_resources_burned[i] == 0 || weights[i] == 0 {
+            continue;
+        }
+
+        let resources_burned = resources_burned[i].min(total_resources_burned[i]);
+
+        lada_reward = lada_reward.try_add(
+            Decimal::from(lada_per_turn)
+                .try_mul(resources_burned)?
+                .try_mul(weights[i] as u64)?
+                .try_div(total_resources_burned[i])?
+                .try_div(total_weight)?,
+        )?;
+    }
+
+    lada_reward.try_floor_u64()
+}
+
+pub fn is_valid_caster_name(name: &str) -> bool {
+    //No leading / trailing spaces, and only characters that are safe to show in the UI and
+    //in the NFT name
+    !name.is_empty()
+        && name.len() <= MAX_CASTER_NAME_LENGTH
+        && name.trim() == name
+        && name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || CASTER_NAME_ALLOWED_SPECIAL_CHARACTERS.contains(c))
+}
+
+pub fn get_caster_name_bytes(name: &str) -> [u8; MAX_CASTER_NAME_LENGTH] {
+    let mut name_bytes = [0; MAX_CASTER_NAME_LENGTH];
+    let length = name.len().min(MAX_CASTER_NAME_LENGTH);
+
+    name_bytes[..length].copy_from_slice(&name.as_bytes()[..length]);
+
+    name_bytes
+}
+
+pub fn get_caster_name(caster: &Caster) -> Option<String> {
+    let length = caster
+        .name
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(MAX_CASTER_NAME_LENGTH);
+
+    if length == 0 {
+        return None;
+    }
+
+    String::from_utf8(caster.name[..length].to_vec()).ok()
+}
+
+pub fn get_cancel_action_refund(resources_burned: u64, refund_percent: u8) -> u64 {
+    (resources_burned as u128 * refund_percent.min(100) as u128 / 100) as u64
+}
+
+pub fn check_caster_transfer(caster: &Caster, player: Pubkey, new_player: Pubkey) -> ProgramResult {
+    if caster.owner != player {
+        return Err(ErrorCode::CasterNotOwned.into());
+    }
+
+    if new_player == player {
+        return Err(ErrorCode::InvalidCasterTransferSamePlayer.into());
+    }
+
+    if caster.turn_commit != None {
+        return Err(ErrorCode::InvalidCasterTransferPendingTurn.into());
+    }
+
+    if caster.modifiers.has_equipped_items() {
+        return Err(ErrorCode::InvalidCasterTransferEquipped.into());
+    }
+
+    Ok(())
+}
+
+//Stats accounts are created the first time they are needed, so this is done every time they are used
+pub fn set_caster_stats_owner(caster_stats: &mut CasterStats, caster: Pubkey, bump: u8) {
+    caster_stats.bump = bump;
+    caster_stats.caster = caster;
+}
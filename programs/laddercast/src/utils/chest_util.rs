@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::account::TurnData;
+use crate::error::ErrorCode;
+use crate::utils::MAX_CHESTS_PER_BATCH;
+
+//Chests have their own LADA each turn, they can't take the LADA of the casters who still have to
+//redeem the turn
+pub fn get_chest_lada(
+    lada_drop: u64,
+    turn_data: &TurnData,
+    chest_lada_per_turn: u64,
+    game_lada: u64,
+) -> u64 {
+    lada_drop
+        .min(chest_lada_per_turn.saturating_sub(turn_data.chest_lada_distributed))
+        .min(game_lada)
+}
+
+//The remaining accounts of a batch start with the chests to open
+pub fn check_chest_batch(chest_count: u8, remaining_accounts: usize) -> ProgramResult {
+    if chest_count > MAX_CHESTS_PER_BATCH {
+        return Err(ErrorCode::TooManyChestsInBatch.into());
+    }
+
+    if chest_count == 0 || remaining_accounts < chest_count as usize {
+        return Err(ErrorCode::ChestAccountMissing.into());
+    }
+
+    Ok(())
+}
@@ -4,15 +4,85 @@ pub const EARTH_INDEX: usize = 2;
 
 //is number * 10 ^ 9 (1 000 000 000) since can only use u64 in transfer
 pub const LADA_DISTRIBUTION_PER_TURN: u64 = 1_984_126_984_130;
+//Fire, water and earth burned weigh the same in the LADA distribution by default
+pub const DEFAULT_LADA_DISTRIBUTION_WEIGHTS: [u16; 3] = [1, 1, 1];
 
 pub const COST_IN_LADA_FOR_CASTER: u16 = 1_000;
 
+//Moving down the ladder costs the destination tile move cost times this multiplier
+pub const DESCEND_MOVE_COST_MULTIPLIER: u64 = 2;
+
+//Share of the caster cost refunded when retiring a max level caster, scales down with level
+pub const MAX_REFUND_PERCENT_FOR_RETIRED_CASTER: u64 = 50;
+
+pub const MAX_CASTER_LEVEL: u8 = 30;
+//Crafted items can't go above this level, the NFT metadata only covers items up to it
+pub const MAX_ITEM_LEVEL: u8 = 30;
+
+//Version 2 added the amulet and ring slots to the modifiers and the consumable effects to the turn commit
+pub const CASTER_VERSION: u8 = 2;
+
+//Permanent bonuses given for each prestige point of a caster
+pub const PRESTIGE_CRITICAL_CHANCE_BONUS: u16 = 25;
+pub const PRESTIGE_MAGIC_FIND_BONUS: u16 = 25;
+pub const PRESTIGE_RESOURCE_BONUS: u16 = 2;
+
+//Bonuses given for each skill point spent in the talent tree, one point is given per level gained
+pub const SKILL_CRITICAL_CHANCE_BONUS: u16 = 10;
+pub const SKILL_MAGIC_FIND_BONUS: u16 = 10;
+pub const SKILL_RESOURCE_BONUS: u16 = 1;
+pub const SKILL_CRAFTING_LUCK_BONUS: u16 = 50;
+
+//Secondary stats of epic and legendary equipment are this % of a main stat of the same feature
+pub const SECONDARY_STAT_VALUE_PERCENT: u16 = 50;
+
+//Number of matching equipment pieces needed for a set bonus
+pub const EQUIPMENT_SET_SIZE: usize = 3;
+
+//Resources given back per item level when salvaging an item, scaled by rarity
+pub const SALVAGE_RESOURCES_PER_LEVEL: u64 = 5;
+
+//Resources burned per item level for the first reforge of an item, doubles with each reforge
+pub const REFORGE_RESOURCES_PER_LEVEL: u64 = 10;
+//Paying this amount of LADA on top of the resources keeps the best of the old and new value
+pub const COST_IN_LADA_FOR_REFORGE: u16 = 10;
+
+//Resources burned per durability point and item level when repairing an item
+pub const REPAIR_RESOURCES_PER_WEAR_PER_LEVEL: u64 = 1;
+
+//One loot table per chest tier
+pub const CHEST_TIERS: usize = 4;
+//Each item is a new account, so the number of items in a chest is limited by the transaction size
+pub const MAX_CHEST_ITEMS: u8 = 6;
+//Chests of a batch and their new items share the accounts of a single transaction
+pub const MAX_CHESTS_PER_BATCH: u8 = 4;
+
+//Casts of the spell books created before charges existed
+pub const DEFAULT_SPELL_BOOK_CHARGES: u8 = 1;
+
+//Resetting the talent tree burns this amount of LADA
+pub const COST_IN_LADA_FOR_RESPEC: u16 = 100;
+
 pub const DECIMALS_PRECISION: u64 = 1_000_000_000;
 
+//Share of the burned resources given back when an action is cancelled during the same turn,
+//used by games that didn't set their own in the game config
+pub const DEFAULT_CANCEL_ACTION_REFUND_PERCENT: u8 = 50;
+
+//Number of turns that can be planned in advance in a caster's action queue
+pub const MAX_QUEUED_TURNS: usize = 8;
+
+//Max number of item keys indexed in a player's inventory, items created past it aren't indexed
+pub const MAX_INVENTORY_ITEMS: usize = 200;
+
 //NFT related
 pub const NFT_MINT_DESCRIPTION: &str = "LC";
 pub const NFT_CASTER_NAME: &str = "Caster";
 
+//Caster names, max length is in bytes and matches the metaplex name max length
+pub const MAX_CASTER_NAME_LENGTH: usize = 32;
+pub const CASTER_NAME_ALLOWED_SPECIAL_CHARACTERS: &str = " -_.'";
+
 //Pub keys
 pub const LADA_MINT_PUBKEY: &str = "LC3JMgeL16vZLrDM9cihX5dfBqbv8DVWLpjEaxvtsqu";
 pub const LADA_ACCOUNT_PUBKEY: &str = "5Q4Pn5w2gNQPBNYvdNgAgWWRiGwGrejjanUZc8z6FPuY";
@@ -25,6 +25,10 @@ pub enum ItemType {
         /// 0-3.6k
         value: u16,
     },
+    /// Stacked into a ConsumableBalance before being used
+    Consumable {
+        consumable_type: ConsumableType,
+    },
 }
 
 impl Default for ItemType {
@@ -62,6 +66,12 @@ pub enum ItemRarity {
     Legendary,
 }
 
+impl Default for ItemRarity {
+    fn default() -> Self {
+        ItemRarity::Common
+    }
+}
+
 #[derive(
 AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy, EnumCount, EnumIter,
 )]
@@ -69,6 +79,8 @@ pub enum EquipmentType {
     Head,
     Robe,
     Staff,
+    Amulet,
+    Ring,
 }
 
 #[derive(
@@ -82,3 +94,61 @@ pub enum SpellType {
     Craft,
     Item,
 }
+
+#[derive(
+AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy, EnumCount, EnumIter,
+)]
+pub enum ConsumableType {
+    /// Next loot is a critical hit
+    CritPotion,
+    /// Next move doesn't burn resources
+    MoveScroll,
+    /// Doubles the chance of finding a chest on the next loot
+    ChestCharm,
+}
+
+/// What a single roll of a chest loot table gives
+#[derive(
+AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy, EnumCount, EnumIter,
+)]
+pub enum LootDrop {
+    SpellBook,
+    Equipment,
+    Consumable,
+    Resources,
+    Lada,
+}
+
+#[derive(
+AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy, EnumCount, EnumIter,
+)]
+pub enum ActionType {
+    Loot,
+    Spell,
+    Move,
+    Crafting,
+}
+
+impl ActionType {
+    /// Index of the action in CommittedActions.action_order
+    pub fn get_action_order_index(&self) -> u8 {
+        match self {
+            ActionType::Loot => 0,
+            ActionType::Spell => 1,
+            ActionType::Move => 2,
+            ActionType::Crafting => 3,
+        }
+    }
+}
+
+#[derive(
+AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy, EnumCount, EnumIter,
+)]
+pub enum SkillType {
+    Fire,
+    Water,
+    Earth,
+    CriticalChance,
+    MagicFind,
+    CraftingLuck,
+}
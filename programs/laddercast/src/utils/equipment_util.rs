@@ -1,8 +1,13 @@
 use anchor_lang::prelude::*;
+use strum::{EnumCount, IntoEnumIterator};
 
-use crate::account::{Game, Item, Player};
+use crate::account::{Caster, Game, Item, Player};
+use crate::error::ErrorCode;
 use crate::utils::{
-    EquipmentType, ItemFeature, ItemRarity, ItemType, RandomEnumValue, RandomValue, SpellType,
+    ConsumableType, EquipmentType, ItemFeature, ItemRarity, ItemType, Modifiers, RandomEnumValue, RandomValue, SecondaryStat,
+    SpellType, DEFAULT_SPELL_BOOK_CHARGES, EARTH_INDEX, FIRE_INDEX, REFORGE_RESOURCES_PER_LEVEL,
+    REPAIR_RESOURCES_PER_WEAR_PER_LEVEL, SALVAGE_RESOURCES_PER_LEVEL, SECONDARY_STAT_VALUE_PERCENT,
+    WATER_INDEX,
 };
 
 pub fn get_item_resource_value(
@@ -63,6 +68,23 @@ pub fn get_item_percentage_value(
     u16::random_within_range(slots, offset, min, max)
 }
 
+pub fn get_item_value(
+    item_feature: ItemFeature,
+    item_rarity: ItemRarity,
+    item_level: u8,
+    slots: &[u8],
+    offset: &mut usize,
+) -> u16 {
+    match item_feature {
+        ItemFeature::Fire | ItemFeature::Water | ItemFeature::Earth => {
+            get_item_resource_value(item_rarity, item_level, slots, offset)
+        }
+        ItemFeature::Power | ItemFeature::Magic => {
+            get_item_percentage_value(item_rarity, item_level, slots, offset)
+        }
+    }
+}
+
 pub fn get_spell_book_value(cost: u16, item_rarity: ItemRarity, multiplier: u16) -> u16 {
     //Multiplier is something that applies to experience only for now (same calculation as the
     //resource spells, but x 2)
@@ -111,6 +133,20 @@ pub fn get_item_rarity(slots: &[u8], offset: &mut usize) -> ItemRarity {
     }
 }
 
+pub fn get_item_rarity_with_floor(
+    slots: &[u8],
+    offset: &mut usize,
+    rarity_floor: ItemRarity,
+) -> ItemRarity {
+    let item_rarity = get_item_rarity(slots, offset);
+
+    if item_rarity < rarity_floor {
+        rarity_floor
+    } else {
+        item_rarity
+    }
+}
+
 pub fn generate_new_equipment(
     item: &mut Account<Item>,
     game: &Account<Game>,
@@ -120,8 +156,6 @@ pub fn generate_new_equipment(
     slots: &[u8],
     offset: &mut usize,
 ) -> ProgramResult {
-    let item_value: u16;
-
     let item_feature = ItemFeature::get_random(slots, offset);
     let new_item_rarity: ItemRarity;
 
@@ -134,33 +168,88 @@ pub fn generate_new_equipment(
         }
     }
 
-    match item_feature {
-        ItemFeature::Fire | ItemFeature::Water | ItemFeature::Earth => {
-            item_value = get_item_resource_value(new_item_rarity, item_level, slots, offset);
-        }
-        ItemFeature::Power | ItemFeature::Magic => {
-            item_value = get_item_percentage_value(new_item_rarity, item_level, slots, offset);
-        }
-    }
+    let item_value = get_item_value(item_feature, new_item_rarity, item_level, slots, offset);
 
     item.game = game.key();
     item.owner = player.key();
     item.equipped_owner = None;
+    //The stored feature is the one the value was scaled for
     item.item_type = ItemType::Equipment {
-        feature: ItemFeature::get_random(slots, offset),
+        feature: item_feature,
         rarity: new_item_rarity,
         equipment_type: EquipmentType::get_random(slots, offset),
         value: item_value,
     };
     item.level = item_level;
+    item.reforge_count = 0;
+    item.wear = 0;
+    item.secondary_stat =
+        generate_secondary_stat(item_feature, new_item_rarity, item_level, slots, offset);
     Ok(())
 }
 
+pub fn get_secondary_stat_value(
+    item_feature: ItemFeature,
+    item_rarity: ItemRarity,
+    item_level: u8,
+    slots: &[u8],
+    offset: &mut usize,
+) -> u16 {
+    let value = get_item_value(item_feature, item_rarity, item_level, slots, offset);
+
+    (value as u32 * SECONDARY_STAT_VALUE_PERCENT as u32 / 100) as u16
+}
+
+pub fn generate_secondary_stat(
+    main_feature: ItemFeature,
+    item_rarity: ItemRarity,
+    item_level: u8,
+    slots: &[u8],
+    offset: &mut usize,
+) -> Option<SecondaryStat> {
+    if item_rarity < ItemRarity::Epic {
+        return None;
+    }
+
+    //Shifting from the main feature makes sure both are different
+    let shift = u8::random_within_range(slots, offset, 1, ItemFeature::COUNT as u8 - 1);
+    let feature = ItemFeature::iter()
+        .nth(((main_feature as u8 + shift) % ItemFeature::COUNT as u8) as usize)
+        .unwrap();
+
+    Some(SecondaryStat {
+        feature,
+        value: get_secondary_stat_value(feature, item_rarity, item_level, slots, offset),
+    })
+}
+
+pub fn get_spell_book_charges(rarity: ItemRarity, slots: &[u8], offset: &mut usize) -> u8 {
+    //Rarer spell books can be cast more times before being destroyed
+    let (min_charges, max_charges) = match rarity {
+        ItemRarity::Common => (1, 1),
+        ItemRarity::Rare => (1, 2),
+        ItemRarity::Epic => (2, 3),
+        ItemRarity::Legendary => (3, 5),
+    };
+
+    u8::random_within_range(slots, offset, min_charges, max_charges)
+}
+
+pub fn get_spell_book_charges_left(item: &Item) -> u8 {
+    //Spell books created before charges existed were destroyed after a single cast
+    if item.spell_book_charges == 0 {
+        DEFAULT_SPELL_BOOK_CHARGES
+    } else {
+        item.spell_book_charges
+    }
+}
+
 pub fn generate_new_spell_book(
     item: &mut Account<Item>,
     game: &Account<Game>,
     player: &Account<Player>,
     item_level: u8,
+    item_rarity: Option<ItemRarity>,
     slots: &[u8],
     offset: &mut usize,
 ) -> ProgramResult {
@@ -171,7 +260,10 @@ pub fn generate_new_spell_book(
         u16::random_within_range(slots, offset, item_level as u16, 10 * item_level as u16);
     let cost_feature: ItemFeature = ItemFeature::get_random_within_range(slots, offset, 2, 4);
 
-    let spell_book_rarity = get_item_rarity(slots, offset);
+    let spell_book_rarity = match item_rarity {
+        None => get_item_rarity(slots, offset),
+        Some(item_rarity) => item_rarity,
+    };
 
     match spell {
         //These values are based on spell book level and spell book rarity
@@ -195,26 +287,329 @@ pub fn generate_new_spell_book(
         rarity: spell_book_rarity,
     };
     item.level = item_level;
+    item.reforge_count = 0;
+    item.wear = 0;
+    item.secondary_stat = None;
+    item.spell_book_charges = get_spell_book_charges(spell_book_rarity, slots, offset);
     Ok(())
 }
 
-pub fn zombify_account(
+pub fn generate_new_consumable(
     item: &mut Account<Item>,
-    authority: AccountInfo,
-    program_id: &Pubkey,
+    game: &Account<Game>,
+    player: &Account<Player>,
+    item_level: u8,
+    slots: &[u8],
+    offset: &mut usize,
 ) -> ProgramResult {
-    // Zombify item so item will be useless if rent is put back in
-    item.item_type = ItemType::Zombie;
+    let consumable_type = ConsumableType::get_random(slots, offset);
+
+    item.game = game.key();
+    item.owner = player.key();
+    item.equipped_owner = None;
+    item.item_type = ItemType::Consumable { consumable_type };
+    item.level = item_level;
+    item.reforge_count = 0;
+    item.wear = 0;
+    item.secondary_stat = None;
+    Ok(())
+}
 
-    // Take that money
-    let dest_starting_lamports = authority.lamports();
-    **authority.lamports.borrow_mut() = dest_starting_lamports
-        .checked_add(item.to_account_info().lamports())
+//Puts the amount in the resource of the feature, in the FIRE_INDEX, WATER_INDEX, EARTH_INDEX order
+pub fn get_resources_for_feature(feature: ItemFeature, amount: u64) -> [u64; 3] {
+    let mut resources: [u64; 3] = [0, 0, 0];
+
+    match feature {
+        ItemFeature::Fire => resources[FIRE_INDEX] = amount,
+        ItemFeature::Water => resources[WATER_INDEX] = amount,
+        ItemFeature::Earth => resources[EARTH_INDEX] = amount,
+        //Non element features are split between all elements, fire gets the remainder
+        ItemFeature::Power | ItemFeature::Magic => {
+            resources = [amount / 3; 3];
+            resources[FIRE_INDEX] += amount % 3;
+        }
+    }
+
+    resources
+}
+
+//Resources minted when salvaging an item, in the FIRE_INDEX, WATER_INDEX, EARTH_INDEX order
+pub fn get_salvage_resources(item_type: &ItemType, item_level: u8) -> Option<[u64; 3]> {
+    let base_amount = SALVAGE_RESOURCES_PER_LEVEL
+        .checked_mul(item_level as u64)
+        .unwrap();
+
+    let (feature, rarity) = match item_type {
+        ItemType::Equipment { feature, rarity, .. } => (feature, rarity),
+        ItemType::SpellBook { cost_feature, rarity, .. } => (cost_feature, rarity),
+        //Unopened chests give a bit of everything
+        ItemType::Chest { .. } => return Some([base_amount; 3]),
+        ItemType::Consumable { .. } | ItemType::Zombie => return None,
+    };
+
+    let amount = base_amount
+        .checked_mul(match rarity {
+            ItemRarity::Common => 1,
+            ItemRarity::Rare => 2,
+            ItemRarity::Epic => 4,
+            ItemRarity::Legendary => 8,
+        })
         .unwrap();
-    **item.to_account_info().lamports.borrow_mut() = 0;
 
-    // Exit and write
-    item.exit(program_id)?;
+    Some(get_resources_for_feature(*feature, amount))
+}
+
+//Resources burned to reforge an item, in the FIRE_INDEX, WATER_INDEX, EARTH_INDEX order
+//The cost doubles with every reforge of the same item
+pub fn get_reforge_cost(item_type: &ItemType, item_level: u8, reforge_count: u8) -> Option<[u64; 3]> {
+    let feature = match item_type {
+        ItemType::Equipment { feature, .. } => feature,
+        _ => return None,
+    };
+
+    let amount = REFORGE_RESOURCES_PER_LEVEL
+        .saturating_mul(item_level as u64)
+        .saturating_mul(2u64.saturating_pow(reforge_count as u32));
+
+    Some(get_resources_for_feature(*feature, amount))
+}
+
+//Re-rolls the value within the item rarity and level range, with LADA the best roll is kept
+pub fn reforge_equipment(
+    item: &mut Item,
+    keep_best_value: bool,
+    slots: &[u8],
+    offset: &mut usize,
+) -> ProgramResult {
+    match item.item_type {
+        ItemType::Equipment {
+            feature,
+            rarity,
+            equipment_type,
+            value,
+        } => {
+            let new_value = get_item_value(feature, rarity, item.level, slots, offset);
+
+            item.item_type = ItemType::Equipment {
+                feature,
+                rarity,
+                equipment_type,
+                value: if keep_best_value { new_value.max(value) } else { new_value },
+            };
+
+            if let Some(secondary_stat) = item.secondary_stat {
+                let new_value = get_secondary_stat_value(
+                    secondary_stat.feature,
+                    rarity,
+                    item.level,
+                    slots,
+                    offset,
+                );
+
+                item.secondary_stat = Some(SecondaryStat {
+                    feature: secondary_stat.feature,
+                    value: if keep_best_value { new_value.max(secondary_stat.value) } else { new_value },
+                });
+            }
+
+            item.reforge_count = item.reforge_count.saturating_add(1);
+
+            Ok(())
+        }
+        _ => Err(ErrorCode::ItemCantBeReforged.into()),
+    }
+}
+
+pub fn get_max_durability(item_rarity: ItemRarity) -> u16 {
+    match item_rarity {
+        ItemRarity::Common => 100,
+        ItemRarity::Rare => 200,
+        ItemRarity::Epic => 300,
+        ItemRarity::Legendary => 500,
+    }
+}
+
+pub fn get_item_durability(item: &Item) -> u16 {
+    match item.item_type {
+        ItemType::Equipment { rarity, .. } => get_max_durability(rarity).saturating_sub(item.wear),
+        _ => 0,
+    }
+}
+
+pub fn is_item_broken(item: &Item) -> bool {
+    get_item_durability(item) == 0
+}
+
+pub fn wear_item(item: &mut Item) {
+    if !is_item_broken(item) {
+        item.wear += 1;
+    }
+}
+
+//Resources burned to repair all the durability lost by an item
+pub fn get_repair_cost(item: &Item) -> Option<[u64; 3]> {
+    match item.item_type {
+        ItemType::Equipment { feature, .. } if item.wear > 0 => {
+            let amount = REPAIR_RESOURCES_PER_WEAR_PER_LEVEL
+                .saturating_mul(item.wear as u64)
+                .saturating_mul(item.level as u64);
+
+            Some(get_resources_for_feature(feature, amount))
+        }
+        _ => None,
+    }
+}
+
+//Every item equipped by the caster needs its account, in the staff, head, robe, amulet, ring order
+pub fn check_equipment_accounts(modifiers: &Modifiers, equipment_keys: [Pubkey; 5]) -> ProgramResult {
+    let equipped_items = [
+        modifiers.staff,
+        modifiers.head,
+        modifiers.robe,
+        modifiers.amulet,
+        modifiers.ring,
+    ];
+
+    for (equipped_item, equipment_key) in equipped_items.iter().zip(equipment_keys.iter()) {
+        if let Some(equipped_item) = equipped_item {
+            if equipped_item != equipment_key {
+                return Err(ErrorCode::LoadoutItemMissing.into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+//Index of the item in Modifiers::get_loadout, None if it can't be equipped
+pub fn get_loadout_slot(item_type: &ItemType) -> Option<usize> {
+    match item_type {
+        ItemType::Equipment { equipment_type, .. } => match equipment_type {
+            EquipmentType::Head => Some(0),
+            EquipmentType::Robe => Some(1),
+            EquipmentType::Staff => Some(2),
+            EquipmentType::Amulet => Some(3),
+            EquipmentType::Ring => Some(4),
+        },
+        ItemType::SpellBook { .. } => Some(5),
+        _ => None,
+    }
+}
+
+pub fn equip_item_to_caster(
+    caster: &mut Caster,
+    caster_key: Pubkey,
+    item: &mut Item,
+    item_key: Pubkey,
+) -> ProgramResult {
+    if item.equipped_owner != None {
+        return Err(ErrorCode::ItemAlreadyInUse.into());
+    }
+
+    if item.level > caster.level {
+        return Err(ErrorCode::ItemLevelTooHigh.into());
+    }
+
+    if caster.turn_commit != None {
+        return Err(ErrorCode::NoEquipUnequipOnPendingTurn.into());
+    }
+
+    let slot = get_loadout_slot(&item.item_type).ok_or(ErrorCode::InvalidEquipItemType)?;
+    let mut loadout = caster.modifiers.get_loadout();
+
+    if loadout[slot] != None {
+        return Err(ErrorCode::ItemTypeAlreadyEquipped.into());
+    }
+
+    loadout[slot] = Some(item_key);
+    caster.modifiers.set_loadout(loadout);
+    item.equipped_owner = Some(caster_key);
+
+    Ok(())
+}
+
+pub fn unequip_item_from_caster(
+    caster: &mut Caster,
+    caster_key: Pubkey,
+    item: &mut Item,
+) -> ProgramResult {
+    if item.equipped_owner != Some(caster_key) {
+        return Err(ErrorCode::ItemNotExists.into());
+    }
+
+    if caster.turn_commit != None {
+        return Err(ErrorCode::NoEquipUnequipOnPendingTurn.into());
+    }
+
+    let slot = get_loadout_slot(&item.item_type).ok_or(ErrorCode::InvalidEquipItemType)?;
+    let mut loadout = caster.modifiers.get_loadout();
+
+    loadout[slot] = None;
+    caster.modifiers.set_loadout(loadout);
+    item.equipped_owner = None;
+
+    Ok(())
+}
+
+//Replaces the whole loadout of a caster, the new items are in the Modifiers::get_loadout order
+//and the default pubkey leaves a slot empty, the items currently equipped are found in the new
+//items or in the other accounts
+pub fn set_caster_loadout<'info>(
+    caster: &mut Caster,
+    caster_key: Pubkey,
+    game: Pubkey,
+    player: Pubkey,
+    new_item_accounts: &[AccountInfo<'info>; 6],
+    other_item_accounts: &[AccountInfo<'info>],
+    program_id: &Pubkey,
+) -> ProgramResult {
+    //Every item is loaded once, since an item can be in both the current and the new loadout
+    let mut items: Vec<Account<Item>> = vec![];
+
+    for item_key in caster.modifiers.get_loadout().iter().flatten() {
+        let item_account = new_item_accounts
+            .iter()
+            .chain(other_item_accounts.iter())
+            .find(|account| account.key() == *item_key)
+            .ok_or(ErrorCode::LoadoutItemMissing)?;
+
+        let mut item: Account<Item> = Account::try_from(item_account)?;
+
+        unequip_item_from_caster(caster, caster_key, &mut item)?;
+        items.push(item);
+    }
+
+    for (slot, item_account) in new_item_accounts.iter().enumerate() {
+        if item_account.key() == Pubkey::default() {
+            continue;
+        }
+
+        let index = match items.iter().position(|item| item.key() == item_account.key()) {
+            Some(index) => index,
+            None => {
+                items.push(Account::try_from(item_account)?);
+                items.len() - 1
+            }
+        };
+
+        let item = &mut items[index];
+
+        if item.game != game || item.owner != player {
+            return Err(ErrorCode::ItemNotOwned.into());
+        }
+
+        if get_loadout_slot(&item.item_type) != Some(slot) {
+            return Err(ErrorCode::InvalidEquipItemType.into());
+        }
+
+        let item_key = item.key();
+        equip_item_to_caster(caster, caster_key, item, item_key)?;
+    }
+
+    for item in items.iter() {
+        item.exit(program_id)?;
+    }
+
     Ok(())
 }
 
@@ -226,11 +621,14 @@ pub fn get_name_for_mint(item_type: &ItemType) -> Option<String> {
             match equipment_type {
                 EquipmentType::Staff => Some("Staff".to_string()),
                 EquipmentType::Robe => Some("Robe".to_string()),
-                EquipmentType::Head => Some("Head".to_string())
+                EquipmentType::Head => Some("Head".to_string()),
+                EquipmentType::Amulet => Some("Amulet".to_string()),
+                EquipmentType::Ring => Some("Ring".to_string())
             }
         }
         ItemType::SpellBook { .. } => Some("Spellbook".to_string()),
         ItemType::Chest { .. } => Some("Chest".to_string()),
-        ItemType::Zombie { .. } => None
+        //Consumables are stacked in a balance, they can't be minted
+        ItemType::Consumable { .. } | ItemType::Zombie { .. } => None
     }
-}
\ No newline at end of file
+}
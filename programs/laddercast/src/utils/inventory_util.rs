@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::account::Inventory;
+use crate::error::ErrorCode;
+use crate::utils::MAX_INVENTORY_ITEMS;
+
+//The inventory is optional, players who didn't create it don't have their items indexed.
+//Instructions take it as an UncheckedAccount with the ["inventory", player.key] seeds, so the PDA
+//is always passed, and it's skipped here until it's created
+pub fn update_inventory(
+    inventory_account: &AccountInfo,
+    player: &Pubkey,
+    added_items: &[Pubkey],
+    removed_items: &[Pubkey],
+    program_id: &Pubkey,
+) -> ProgramResult {
+    if inventory_account.data_is_empty() {
+        return Ok(());
+    }
+
+    let mut inventory: Account<Inventory> = Account::try_from(inventory_account)?;
+
+    if inventory.player != *player {
+        return Err(ErrorCode::InventoryNotOwned.into());
+    }
+
+    for item in removed_items.iter() {
+        remove_inventory_item(&mut inventory, item);
+    }
+
+    for item in added_items.iter() {
+        add_inventory_item(&mut inventory, *item);
+    }
+
+    inventory.exit(program_id)
+}
+
+//The inventory is only an index, a full inventory stops indexing new items instead of blocking
+//the game, returns if the item is indexed
+pub fn add_inventory_item(inventory: &mut Inventory, item: Pubkey) -> bool {
+    if inventory.items.contains(&item) {
+        return true;
+    }
+
+    if inventory.items.len() >= MAX_INVENTORY_ITEMS {
+        return false;
+    }
+
+    inventory.items.push(item);
+
+    true
+}
+
+pub fn remove_inventory_item(inventory: &mut Inventory, item: &Pubkey) {
+    inventory.items.retain(|key| key != item);
+}
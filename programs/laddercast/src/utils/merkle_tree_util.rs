@@ -1,13 +1,19 @@
 use crate::{ItemFeature, ItemType};
 use crate::account::{Caster, Item};
-use crate::utils::{EquipmentType, ItemRarity, SpellType};
+use crate::utils::{
+    get_spell_book_charges_left, EquipmentType, ItemRarity, SpellType, DEFAULT_SPELL_BOOK_CHARGES,
+};
 
 //Format for merkle strings
 //
 // Chest: {uri}:chest:{item_level}:{tier}
 // Spellbook: {uri}:spellbook:{item_level}:{spell_type}:{cost_feature}:{rarity}:{cost}:{value}
+//  followed by :{charges} if the spell book has more or less charges left than the default
 // Equipment: {uri}:{equipment_type}:{item_level}:{feature}:{rarity}:{value}
-// Caster: {uri}:caster:{version}:{level}
+//  followed by :{secondary_feature}:{secondary_value} for epic and legendary equipment with a secondary stat
+//  followed by :{wear} if the equipment was used
+// Caster: {uri}:caster:{version}:{level}, the name of renamed casters is only in the NFT metadata
+//  the version is the one of the NFT metadata, it doesn't follow the caster account layout
 
 const SEPARATOR: &str = ":";
 const CHEST_NAME: &str = "chest";
@@ -15,7 +21,10 @@ const SPELLBOOK_NAME: &str = "spellbook";
 const ROBE_NAME: &str = "robe";
 const HEAD_NAME: &str = "head";
 const STAFF_NAME: &str = "staff";
+const AMULET_NAME: &str = "amulet";
+const RING_NAME: &str = "ring";
 const CASTER_NAME: &str = "caster";
+const CASTER_METADATA_VERSION: u8 = 1;
 
 fn get_merkle_string_for_spell_type(spell: SpellType) -> String {
     match spell {
@@ -51,7 +60,9 @@ fn get_merkle_string_for_equipment_type(equipment_type: EquipmentType) -> String
     match equipment_type {
         EquipmentType::Head => HEAD_NAME.to_string(),
         EquipmentType::Robe => ROBE_NAME.to_string(),
-        EquipmentType::Staff => STAFF_NAME.to_string()
+        EquipmentType::Staff => STAFF_NAME.to_string(),
+        EquipmentType::Amulet => AMULET_NAME.to_string(),
+        EquipmentType::Ring => RING_NAME.to_string()
     }
 }
 
@@ -88,6 +99,12 @@ fn get_merkle_string_for_spell_book(uri: &str, item: Item, spell: SpellType, cos
     merkle_string.push_str(SEPARATOR);
     merkle_string.push_str(&value.to_string());
 
+    let charges = get_spell_book_charges_left(&item);
+    if charges != DEFAULT_SPELL_BOOK_CHARGES {
+        merkle_string.push_str(SEPARATOR);
+        merkle_string.push_str(&charges.to_string());
+    }
+
     merkle_string
 }
 
@@ -106,6 +123,18 @@ fn get_merkle_string_for_equipment(uri: &str, item: Item, equipment_type: Equipm
     merkle_string.push_str(SEPARATOR);
     merkle_string.push_str(&value.to_string());
 
+    if let Some(secondary_stat) = item.secondary_stat {
+        merkle_string.push_str(SEPARATOR);
+        merkle_string.push_str(&get_merkle_string_for_item_feature(secondary_stat.feature));
+        merkle_string.push_str(SEPARATOR);
+        merkle_string.push_str(&secondary_stat.value.to_string());
+    }
+
+    if item.wear > 0 {
+        merkle_string.push_str(SEPARATOR);
+        merkle_string.push_str(&item.wear.to_string());
+    }
+
     merkle_string
 }
 
@@ -133,7 +162,7 @@ pub fn get_merkle_string_for_caster(uri: &str, caster: Caster) -> String {
     merkle_string.push_str(SEPARATOR);
     merkle_string.push_str(CASTER_NAME);
     merkle_string.push_str(SEPARATOR);
-    merkle_string.push_str(&caster.version.to_string());
+    merkle_string.push_str(&CASTER_METADATA_VERSION.to_string());
     merkle_string.push_str(SEPARATOR);
     merkle_string.push_str(&caster.level.to_string());
 
@@ -1,13 +1,18 @@
+pub use account_util::*;
 pub use accounts::*;
+pub use action_queue_util::*;
 pub use caster_util::*;
+pub use chest_util::*;
 pub use constants::*;
 pub use enums::*;
 pub use equipment_util::*;
+pub use inventory_util::*;
 pub use merkle_tree_util::*;
 pub use player_util::*;
 pub use random_util::*;
 pub use tests::*;
 pub use tile_util::*;
+pub use token_util::*;
 pub use vector_util::*;
 
 pub mod enums;
@@ -20,4 +25,9 @@ pub mod caster_util;
 pub mod vector_util;
 pub mod player_util;
 pub mod accounts;
-pub mod merkle_tree_util;
\ No newline at end of file
+pub mod merkle_tree_util;
+pub mod token_util;
+pub mod account_util;
+pub mod action_queue_util;
+pub mod chest_util;
+pub mod inventory_util;
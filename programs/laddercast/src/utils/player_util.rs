@@ -1,10 +1,15 @@
 use anchor_lang::{Key, ToAccountInfo};
 use anchor_lang::prelude::Account;
 use anchor_lang::prelude::ProgramError;
+use anchor_lang::prelude::Pubkey;
 
 use crate::{ItemFeature, ItemType, PlayerBonuses};
 use crate::account::{Caster, Game, Item, Player};
-use crate::utils::{EquipmentType, Modifiers};
+use crate::utils::{
+    is_item_broken, EquipmentType, ItemRarity, Modifiers, SetBonuses, SkillAllocation, PRESTIGE_CRITICAL_CHANCE_BONUS,
+    PRESTIGE_MAGIC_FIND_BONUS, PRESTIGE_RESOURCE_BONUS, SKILL_CRAFTING_LUCK_BONUS,
+    SKILL_CRITICAL_CHANCE_BONUS, SKILL_MAGIC_FIND_BONUS, SKILL_RESOURCE_BONUS,
+};
 
 pub fn get_player_bonuses(
     modifiers: &Modifiers,
@@ -12,6 +17,7 @@ pub fn get_player_bonuses(
     game: &Account<Game>,
     player: &Account<Player>,
     caster: &Account<Caster>,
+    set_bonuses: &SetBonuses,
 ) -> PlayerBonuses {
     let mut player_bonuses: PlayerBonuses = PlayerBonuses {
         critical_chance: 200, //By default they have 2% chance of critical strike
@@ -19,49 +25,150 @@ pub fn get_player_bonuses(
         fire_chance: 0,
         water_chance: 0,
         earth_chance: 0,
+        crafting_chance: 0,
     };
 
+    let mut equipment_bonuses = PlayerBonuses::default();
+    let mut equipment_keys: Vec<Pubkey> = vec![];
+    let mut set_pieces: Vec<(ItemFeature, ItemRarity)> = vec![];
+
     for item in item_accounts {
         match item {
             Ok(equipment) => {
-                if equipment.owner != player.to_account_info().key()
-                    || equipment.equipped_owner.unwrap() != caster.to_account_info().key()
-                    || equipment.game != game.to_account_info().key()
+                //The same item passed twice only counts once
+                if !is_equipment_contributing(modifiers, equipment, game, player, caster)
+                    || equipment_keys.contains(&equipment.key())
                 {
                     continue;
                 }
 
-                if let ItemType::Equipment { feature, value, equipment_type, .. } = equipment.item_type {
-                    match equipment_type {
-                        EquipmentType::Head => {
-                            if modifiers.head == None || modifiers.head.unwrap() != equipment.to_account_info().key() {
-                                continue;
-                            }
-                        }
-                        EquipmentType::Staff => {
-                            if modifiers.staff == None || modifiers.staff.unwrap() != equipment.to_account_info().key() {
-                                continue;
-                            }
-                        }
-                        EquipmentType::Robe => {
-                            if modifiers.robe == None || modifiers.robe.unwrap() != equipment.to_account_info().key() {
-                                continue;
-                            }
-                        }
-                    }
+                if let ItemType::Equipment { feature, rarity, value, .. } = equipment.item_type {
+                    add_feature_bonus(&mut equipment_bonuses, feature, value);
 
-                    match feature {
-                        ItemFeature::Fire => player_bonuses.fire_chance += value,
-                        ItemFeature::Water => player_bonuses.water_chance += value,
-                        ItemFeature::Earth => player_bonuses.earth_chance += value,
-                        ItemFeature::Magic => player_bonuses.magic_find_chance += value,
-                        ItemFeature::Power => player_bonuses.critical_chance += value,
+                    if let Some(secondary_stat) = equipment.secondary_stat {
+                        add_feature_bonus(
+                            &mut equipment_bonuses,
+                            secondary_stat.feature,
+                            secondary_stat.value,
+                        );
                     }
+
+                    equipment_keys.push(equipment.key());
+                    set_pieces.push((feature, rarity));
                 }
             }
             Err(_) => {}
         }
     }
 
+    apply_set_bonus(&mut equipment_bonuses, set_bonuses.get_bonus_percent(&set_pieces));
+
+    player_bonuses.critical_chance += equipment_bonuses.critical_chance;
+    player_bonuses.magic_find_chance += equipment_bonuses.magic_find_chance;
+    player_bonuses.fire_chance += equipment_bonuses.fire_chance;
+    player_bonuses.water_chance += equipment_bonuses.water_chance;
+    player_bonuses.earth_chance += equipment_bonuses.earth_chance;
+
+    apply_prestige_bonuses(&mut player_bonuses, caster.prestige);
+    apply_skill_bonuses(&mut player_bonuses, &caster.skills);
+
     player_bonuses
 }
+
+pub fn add_feature_bonus(player_bonuses: &mut PlayerBonuses, feature: ItemFeature, value: u16) {
+    match feature {
+        ItemFeature::Fire => player_bonuses.fire_chance += value,
+        ItemFeature::Water => player_bonuses.water_chance += value,
+        ItemFeature::Earth => player_bonuses.earth_chance += value,
+        ItemFeature::Magic => player_bonuses.magic_find_chance += value,
+        ItemFeature::Power => player_bonuses.critical_chance += value,
+    }
+}
+
+//Equipment only gives bonuses if it's equipped in its slot by the caster and isn't broken
+pub fn is_equipment_contributing(
+    modifiers: &Modifiers,
+    equipment: &Account<Item>,
+    game: &Account<Game>,
+    player: &Account<Player>,
+    caster: &Account<Caster>,
+) -> bool {
+    if equipment.owner != player.to_account_info().key()
+        || equipment.equipped_owner != Some(caster.to_account_info().key())
+        || equipment.game != game.to_account_info().key()
+        || is_item_broken(equipment)
+    {
+        return false;
+    }
+
+    match equipment.item_type {
+        ItemType::Equipment { equipment_type, .. } => {
+            let slot = match equipment_type {
+                EquipmentType::Head => modifiers.head,
+                EquipmentType::Staff => modifiers.staff,
+                EquipmentType::Robe => modifiers.robe,
+                EquipmentType::Amulet => modifiers.amulet,
+                EquipmentType::Ring => modifiers.ring,
+            };
+
+            slot == Some(equipment.to_account_info().key())
+        }
+        _ => false,
+    }
+}
+
+//Matching equipment boosts every bonus given by the equipment by a %
+pub fn apply_set_bonus(equipment_bonuses: &mut PlayerBonuses, bonus_percent: u16) {
+    let apply = |value: u16| -> u16 {
+        let bonus = value as u32 * bonus_percent as u32 / 100;
+
+        value.saturating_add(bonus.min(u16::MAX as u32) as u16)
+    };
+
+    equipment_bonuses.critical_chance = apply(equipment_bonuses.critical_chance);
+    equipment_bonuses.magic_find_chance = apply(equipment_bonuses.magic_find_chance);
+    equipment_bonuses.fire_chance = apply(equipment_bonuses.fire_chance);
+    equipment_bonuses.water_chance = apply(equipment_bonuses.water_chance);
+    equipment_bonuses.earth_chance = apply(equipment_bonuses.earth_chance);
+}
+
+pub fn apply_prestige_bonuses(player_bonuses: &mut PlayerBonuses, prestige: u8) {
+    let prestige = prestige as u16;
+
+    player_bonuses.critical_chance = player_bonuses
+        .critical_chance
+        .saturating_add(prestige.saturating_mul(PRESTIGE_CRITICAL_CHANCE_BONUS));
+    player_bonuses.magic_find_chance = player_bonuses
+        .magic_find_chance
+        .saturating_add(prestige.saturating_mul(PRESTIGE_MAGIC_FIND_BONUS));
+    player_bonuses.fire_chance = player_bonuses
+        .fire_chance
+        .saturating_add(prestige.saturating_mul(PRESTIGE_RESOURCE_BONUS));
+    player_bonuses.water_chance = player_bonuses
+        .water_chance
+        .saturating_add(prestige.saturating_mul(PRESTIGE_RESOURCE_BONUS));
+    player_bonuses.earth_chance = player_bonuses
+        .earth_chance
+        .saturating_add(prestige.saturating_mul(PRESTIGE_RESOURCE_BONUS));
+}
+
+pub fn apply_skill_bonuses(player_bonuses: &mut PlayerBonuses, skills: &SkillAllocation) {
+    player_bonuses.critical_chance = player_bonuses
+        .critical_chance
+        .saturating_add((skills.critical_chance as u16).saturating_mul(SKILL_CRITICAL_CHANCE_BONUS));
+    player_bonuses.magic_find_chance = player_bonuses
+        .magic_find_chance
+        .saturating_add((skills.magic_find as u16).saturating_mul(SKILL_MAGIC_FIND_BONUS));
+    player_bonuses.fire_chance = player_bonuses
+        .fire_chance
+        .saturating_add((skills.fire as u16).saturating_mul(SKILL_RESOURCE_BONUS));
+    player_bonuses.water_chance = player_bonuses
+        .water_chance
+        .saturating_add((skills.water as u16).saturating_mul(SKILL_RESOURCE_BONUS));
+    player_bonuses.earth_chance = player_bonuses
+        .earth_chance
+        .saturating_add((skills.earth as u16).saturating_mul(SKILL_RESOURCE_BONUS));
+    player_bonuses.crafting_chance = player_bonuses
+        .crafting_chance
+        .saturating_add((skills.crafting_luck as u16).saturating_mul(SKILL_CRAFTING_LUCK_BONUS));
+}
@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod test_internal_functions {
+    use anchor_lang::__private::CLOSED_ACCOUNT_DISCRIMINATOR;
+    use anchor_lang::prelude::{Account, AccountInfo, Pubkey};
+    use anchor_lang::AccountSerialize;
+
+    use crate::account::Item;
+    use crate::utils::{
+        close_item_account, create_chest_for_testing, create_equipment_for_testing,
+        finish_redeem_item, EquipmentType,
+    };
+
+    #[test]
+    fn test_close_item_account_cannot_be_revived() {
+        let item_key = Pubkey::new_unique();
+        let mut item_lamports: u64 = 1_000;
+        let mut item_data = vec![0; Item::SIZE];
+        create_chest_for_testing().try_serialize(&mut item_data.as_mut_slice()).unwrap();
+        let item_info = AccountInfo::new(&item_key, false, true, &mut item_lamports, &mut item_data, &crate::ID, false, 0);
+
+        let destination_key = Pubkey::new_unique();
+        let mut destination_lamports: u64 = 10;
+        let mut destination_data = vec![];
+        let destination_info = AccountInfo::new(&destination_key, true, true, &mut destination_lamports, &mut destination_data, &destination_key, false, 0);
+
+        let item: Account<Item> = Account::try_from(&item_info).unwrap();
+
+        close_item_account(&item, destination_info.clone()).unwrap();
+
+        assert_eq!(item_info.lamports(), 0);
+        assert_eq!(destination_info.lamports(), 1_010);
+        assert!(item_info.data.borrow()[8..].iter().all(|byte| *byte == 0));
+
+        //Rent put back in the same transaction
+        **item_info.lamports.borrow_mut() = 1_000;
+
+        assert!(Account::<Item>::try_from(&item_info).is_err());
+    }
+
+    #[test]
+    fn test_finish_redeem_item_unused() {
+        //Freshly created item account, like in the redeem instructions
+        let item_key = Pubkey::new_unique();
+        let mut item_lamports: u64 = 1_000;
+        let mut item_data = vec![0; Item::SIZE];
+        let item_info = AccountInfo::new(&item_key, false, true, &mut item_lamports, &mut item_data, &crate::ID, false, 0);
+
+        let destination_key = Pubkey::new_unique();
+        let mut destination_lamports: u64 = 10;
+        let mut destination_data = vec![];
+        let destination_info = AccountInfo::new(&destination_key, true, true, &mut destination_lamports, &mut destination_data, &destination_key, false, 0);
+
+        let item: Account<Item> = Account::try_from_unchecked(&item_info).unwrap();
+
+        finish_redeem_item(item, false, &destination_info, &crate::ID).unwrap();
+
+        assert_eq!(item_info.lamports(), 0);
+        assert_eq!(destination_info.lamports(), 1_010);
+        assert_eq!(item_info.data.borrow()[..8], CLOSED_ACCOUNT_DISCRIMINATOR);
+
+        //Rent put back in the same transaction
+        **item_info.lamports.borrow_mut() = 1_000;
+
+        assert!(Account::<Item>::try_from(&item_info).is_err());
+    }
+
+    #[test]
+    fn test_finish_redeem_item_used() {
+        let item_key = Pubkey::new_unique();
+        let mut item_lamports: u64 = 1_000;
+        let mut item_data = vec![0; Item::SIZE];
+        let item_info = AccountInfo::new(&item_key, false, true, &mut item_lamports, &mut item_data, &crate::ID, false, 0);
+
+        let destination_key = Pubkey::new_unique();
+        let mut destination_lamports: u64 = 10;
+        let mut destination_data = vec![];
+        let destination_info = AccountInfo::new(&destination_key, true, true, &mut destination_lamports, &mut destination_data, &destination_key, false, 0);
+
+        let equipment = create_equipment_for_testing(EquipmentType::Staff);
+        let mut item: Account<Item> = Account::try_from_unchecked(&item_info).unwrap();
+        item.item_type = equipment.item_type;
+
+        finish_redeem_item(item, true, &destination_info, &crate::ID).unwrap();
+
+        assert_eq!(item_info.lamports(), 1_000);
+        assert_eq!(destination_info.lamports(), 10);
+        assert_eq!(Account::<Item>::try_from(&item_info).unwrap().item_type, equipment.item_type);
+    }
+}
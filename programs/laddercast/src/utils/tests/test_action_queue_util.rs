@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod test_internal_functions {
+    use anchor_lang::__private::CLOSED_ACCOUNT_DISCRIMINATOR;
+    use anchor_lang::prelude::{AccountInfo, Pubkey};
+    use anchor_lang::AccountSerialize;
+
+    use crate::account::{ActionQueue, Player};
+    use crate::utils::{close_action_queue, set_action_queue_budget, spend_action_queue_budget};
+
+    fn create_player_for_testing() -> Player {
+        Player {
+            authority: Pubkey::new_unique(),
+            game: Pubkey::new_unique(),
+            bump: 0,
+            item_counter: 0,
+            queued_budget: [0; 3],
+            chests_opened: 0,
+        }
+    }
+
+    fn create_action_queue_for_testing() -> ActionQueue {
+        ActionQueue {
+            bump: 0,
+            caster: Pubkey::new_unique(),
+            plans: vec![],
+            budget: [0; 3],
+        }
+    }
+
+    #[test]
+    fn test_set_action_queue_budget() {
+        let mut player = create_player_for_testing();
+        let mut action_queue_1 = create_action_queue_for_testing();
+        let mut action_queue_2 = create_action_queue_for_testing();
+
+        set_action_queue_budget(&mut player, &mut action_queue_1, [10, 20, 30]).unwrap();
+        set_action_queue_budget(&mut player, &mut action_queue_2, [5, 0, 5]).unwrap();
+
+        //Queues of all casters are approved together
+        assert_eq!(player.queued_budget, [15, 20, 35]);
+
+        set_action_queue_budget(&mut player, &mut action_queue_1, [1, 2, 3]).unwrap();
+
+        assert_eq!(action_queue_1.budget, [1, 2, 3]);
+        assert_eq!(player.queued_budget, [6, 2, 8]);
+
+        //Cancelling a queue doesn't touch the others
+        set_action_queue_budget(&mut player, &mut action_queue_1, [0; 3]).unwrap();
+
+        assert_eq!(player.queued_budget, [5, 0, 5]);
+        assert_eq!(action_queue_2.budget, [5, 0, 5]);
+    }
+
+    #[test]
+    fn test_set_action_queue_budget_overflow() {
+        let mut player = create_player_for_testing();
+        let mut action_queue_1 = create_action_queue_for_testing();
+        let mut action_queue_2 = create_action_queue_for_testing();
+
+        set_action_queue_budget(&mut player, &mut action_queue_1, [u64::MAX, 0, 0]).unwrap();
+
+        assert!(set_action_queue_budget(&mut player, &mut action_queue_2, [1, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn test_spend_action_queue_budget() {
+        let mut player = create_player_for_testing();
+        let mut action_queue_1 = create_action_queue_for_testing();
+        let mut action_queue_2 = create_action_queue_for_testing();
+
+        set_action_queue_budget(&mut player, &mut action_queue_1, [10, 20, 30]).unwrap();
+        set_action_queue_budget(&mut player, &mut action_queue_2, [5, 5, 5]).unwrap();
+
+        spend_action_queue_budget(&mut player, &mut action_queue_1, [10, 0, 7]).unwrap();
+
+        assert_eq!(action_queue_1.budget, [0, 20, 23]);
+        assert_eq!(player.queued_budget, [5, 25, 28]);
+    }
+
+    #[test]
+    fn test_spend_action_queue_budget_exceeded() {
+        let mut player = create_player_for_testing();
+        let mut action_queue_1 = create_action_queue_for_testing();
+        let mut action_queue_2 = create_action_queue_for_testing();
+
+        set_action_queue_budget(&mut player, &mut action_queue_1, [10, 20, 30]).unwrap();
+        set_action_queue_budget(&mut player, &mut action_queue_2, [50, 50, 50]).unwrap();
+
+        //The other queue's budget can't be used
+        assert!(spend_action_queue_budget(&mut player, &mut action_queue_1, [11, 0, 0]).is_err());
+        assert_eq!(action_queue_1.budget, [10, 20, 30]);
+        assert_eq!(player.queued_budget, [60, 70, 80]);
+    }
+
+    #[test]
+    fn test_close_action_queue_of_retired_caster() {
+        let mut player = create_player_for_testing();
+        let mut action_queue = create_action_queue_for_testing();
+        let mut other_action_queue = create_action_queue_for_testing();
+
+        set_action_queue_budget(&mut player, &mut action_queue, [10, 20, 30]).unwrap();
+        set_action_queue_budget(&mut player, &mut other_action_queue, [5, 5, 5]).unwrap();
+
+        let action_queue_key = Pubkey::new_unique();
+        let mut action_queue_lamports: u64 = 1_000;
+        let mut action_queue_data = vec![0; ActionQueue::SIZE];
+        action_queue.try_serialize(&mut action_queue_data.as_mut_slice()).unwrap();
+        let action_queue_info = AccountInfo::new(&action_queue_key, false, true, &mut action_queue_lamports, &mut action_queue_data, &crate::ID, false, 0);
+
+        let authority_key = Pubkey::new_unique();
+        let mut authority_lamports: u64 = 10;
+        let mut authority_data = vec![];
+        let authority_info = AccountInfo::new(&authority_key, true, true, &mut authority_lamports, &mut authority_data, &authority_key, false, 0);
+
+        close_action_queue(&mut player, &action_queue_info, &authority_info, &crate::ID).unwrap();
+
+        //Only the budget of the other caster's queue is left
+        assert_eq!(player.queued_budget, [5, 5, 5]);
+        assert_eq!(action_queue_info.lamports(), 0);
+        assert_eq!(authority_info.lamports(), 1_010);
+        assert_eq!(action_queue_info.data.borrow()[..8], CLOSED_ACCOUNT_DISCRIMINATOR);
+    }
+
+    #[test]
+    fn test_close_action_queue_missing() {
+        let mut player = create_player_for_testing();
+        player.queued_budget = [5, 5, 5];
+
+        //Casters that never had planned turns don't have a queue
+        let action_queue_key = Pubkey::new_unique();
+        let system_program = Pubkey::default();
+        let mut action_queue_lamports: u64 = 0;
+        let mut action_queue_data = vec![];
+        let action_queue_info = AccountInfo::new(&action_queue_key, false, true, &mut action_queue_lamports, &mut action_queue_data, &system_program, false, 0);
+
+        let authority_key = Pubkey::new_unique();
+        let mut authority_lamports: u64 = 10;
+        let mut authority_data = vec![];
+        let authority_info = AccountInfo::new(&authority_key, true, true, &mut authority_lamports, &mut authority_data, &authority_key, false, 0);
+
+        close_action_queue(&mut player, &action_queue_info, &authority_info, &crate::ID).unwrap();
+
+        assert_eq!(player.queued_budget, [5, 5, 5]);
+        assert_eq!(authority_info.lamports(), 10);
+    }
+}
@@ -2,10 +2,22 @@
 mod test_internal_functions {
     use std::convert::TryInto;
 
+    use anchor_lang::prelude::{AnchorDeserialize, AnchorSerialize, Pubkey};
     use lazy_static::lazy_static;
     use rand::random;
 
-    use crate::utils::{create_caster_for_testing, give_exp_to_caster_resources_burned, give_exp_to_caster_spell, is_spell_successful, ItemRarity};
+    use crate::error::ErrorCode;
+    use crate::utils::{
+        allocate_skill_points, check_caster_transfer, create_caster_for_testing,
+        get_cancel_action_refund, get_caster_name, get_caster_name_bytes,
+        get_lada_refund_for_retired_caster, get_lada_reward_for_resources_burned,
+        give_exp_to_caster_resources_burned, give_exp_to_caster_spell, is_spell_successful,
+        is_valid_caster_name, migrate_legacy_caster, reset_caster_experience_for_mint,
+        reset_caster_for_prestige, reset_caster_skills, ExperienceCurve, ExperienceCurveMode,
+        ItemRarity, LegacyCaster, LegacyCommittedActions, LegacyModifiers, LegacyTurnCommit,
+        Modifiers, SkillAllocation, SkillType, TurnCommit, CASTER_VERSION, DECIMALS_PRECISION,
+        MAX_CASTER_LEVEL,
+    };
 
     lazy_static! {
         static ref SLOT_HASHES: [u8; 512 * 40] = generate_slot_hashes(true).try_into().unwrap();
@@ -23,7 +35,7 @@ mod test_internal_functions {
     fn test_give_exp_to_caster_resources_burned_no_level_up() {
         let mut caster = create_caster_for_testing();
 
-        give_exp_to_caster_resources_burned(&mut caster, Some(10), Some(20), Some(30));
+        give_exp_to_caster_resources_burned(&mut caster, Some(10), Some(20), Some(30), &ExperienceCurve::default());
 
         assert_eq!(caster.experience, 60);
         assert_eq!(caster.level, 1)
@@ -33,7 +45,7 @@ mod test_internal_functions {
     fn test_give_exp_to_caster_resources_burned_with_level_up() {
         let mut caster = create_caster_for_testing();
 
-        give_exp_to_caster_resources_burned(&mut caster, Some(1600), None, None);
+        give_exp_to_caster_resources_burned(&mut caster, Some(1600), None, None, &ExperienceCurve::default());
 
         assert_eq!(caster.experience, 1600);
         assert_eq!(caster.level, 2)
@@ -44,7 +56,7 @@ mod test_internal_functions {
         let mut caster = create_caster_for_testing();
         caster.level = 30;
 
-        give_exp_to_caster_resources_burned(&mut caster, Some(2400), None, None);
+        give_exp_to_caster_resources_burned(&mut caster, Some(2400), None, None, &ExperienceCurve::default());
 
         assert_eq!(caster.experience, 2400);
         assert_eq!(caster.level, 30)
@@ -54,7 +66,7 @@ mod test_internal_functions {
     fn test_give_exp_to_caster_spell_no_level_up() {
         let mut caster = create_caster_for_testing();
 
-        give_exp_to_caster_spell(&mut caster, 10);
+        give_exp_to_caster_spell(&mut caster, 10, &ExperienceCurve::default());
 
         assert_eq!(caster.experience, 10);
         assert_eq!(caster.level, 1)
@@ -64,7 +76,7 @@ mod test_internal_functions {
     fn test_give_exp_to_caster_spell_with_level_up() {
         let mut caster = create_caster_for_testing();
 
-        give_exp_to_caster_spell(&mut caster, 1600);
+        give_exp_to_caster_spell(&mut caster, 1600, &ExperienceCurve::default());
 
         assert_eq!(caster.experience, 1600);
         assert_eq!(caster.level, 2)
@@ -74,10 +86,11 @@ mod test_internal_functions {
     fn test_give_exp_to_caster_spell_with_3_level_up() {
         let mut caster = create_caster_for_testing();
 
-        give_exp_to_caster_spell(&mut caster, 15000);
+        give_exp_to_caster_spell(&mut caster, 15000, &ExperienceCurve::default());
 
         assert_eq!(caster.experience, 15000);
-        assert_eq!(caster.level, 4)
+        assert_eq!(caster.level, 4);
+        assert_eq!(caster.skill_points, 3)
     }
 
 
@@ -86,10 +99,65 @@ mod test_internal_functions {
         let mut caster = create_caster_for_testing();
         caster.level = 30;
 
-        give_exp_to_caster_spell(&mut caster, 2400);
+        give_exp_to_caster_spell(&mut caster, 2400, &ExperienceCurve::default());
 
         assert_eq!(caster.experience, 2400);
-        assert_eq!(caster.level, 30)
+        assert_eq!(caster.level, 30);
+        assert_eq!(caster.skill_points, 0)
+    }
+
+    #[test]
+    fn test_give_exp_to_caster_spell_with_custom_level_cap() {
+        let mut caster = create_caster_for_testing();
+        let experience_curve = ExperienceCurve {
+            level_cap: 3,
+            mode: ExperienceCurveMode::Formula {
+                multiplier: 100,
+                exponent: 2,
+            },
+        };
+
+        give_exp_to_caster_spell(&mut caster, 150, &experience_curve);
+
+        assert_eq!(caster.level, 2);
+
+        //Level 3 is the cap, even if there is enough experience for more
+        give_exp_to_caster_spell(&mut caster, 10000, &experience_curve);
+
+        assert_eq!(caster.level, 3);
+    }
+
+    #[test]
+    fn test_reset_caster_experience_for_mint() {
+        let mut caster = create_caster_for_testing();
+        let experience_curve = ExperienceCurve {
+            level_cap: 3,
+            mode: ExperienceCurveMode::Formula {
+                multiplier: 100,
+                exponent: 2,
+            },
+        };
+
+        caster.level = 2;
+        caster.experience = 350;
+
+        reset_caster_experience_for_mint(&mut caster, &experience_curve);
+
+        assert_eq!(caster.level, 2);
+        assert_eq!(caster.experience, 100);
+
+        //Burning the NFT back doesn't level the caster up
+        give_exp_to_caster_spell(&mut caster, 0, &experience_curve);
+        assert_eq!(caster.level, 2);
+
+        //Levels above the cap of the game are brought back to it
+        caster.level = 5;
+        caster.experience = 3_000;
+
+        reset_caster_experience_for_mint(&mut caster, &experience_curve);
+
+        assert_eq!(caster.level, 3);
+        assert_eq!(caster.experience, 400);
     }
 
     #[test]
@@ -102,4 +170,296 @@ mod test_internal_functions {
         //No really good way to test it except to make sure it doesn't error out
         assert!(is_success || !is_success);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_get_lada_refund_for_retired_caster() {
+        assert_eq!(get_lada_refund_for_retired_caster(30, MAX_CASTER_LEVEL), 500 * DECIMALS_PRECISION);
+        assert_eq!(get_lada_refund_for_retired_caster(15, MAX_CASTER_LEVEL), 250 * DECIMALS_PRECISION);
+        assert_eq!(get_lada_refund_for_retired_caster(3, MAX_CASTER_LEVEL), 50 * DECIMALS_PRECISION);
+    }
+
+    #[test]
+    fn test_get_lada_refund_for_retired_caster_custom_level_cap() {
+        assert_eq!(get_lada_refund_for_retired_caster(10, 10), 500 * DECIMALS_PRECISION);
+        assert_eq!(get_lada_refund_for_retired_caster(5, 10), 250 * DECIMALS_PRECISION);
+    }
+
+    #[test]
+    fn test_get_lada_refund_for_retired_caster_above_max_level() {
+        assert_eq!(
+            get_lada_refund_for_retired_caster(40, MAX_CASTER_LEVEL),
+            get_lada_refund_for_retired_caster(30, MAX_CASTER_LEVEL)
+        );
+    }
+
+    #[test]
+    fn test_reset_caster_for_prestige() {
+        let mut caster = create_caster_for_testing();
+        caster.level = 30;
+        caster.experience = 8_000_000;
+        caster.modifiers.tile_level = 29;
+        caster.modifiers.tile_column = 2;
+        caster.skill_points = 4;
+        caster.skills.fire = 25;
+
+        reset_caster_for_prestige(&mut caster);
+
+        assert_eq!(caster.level, 1);
+        assert_eq!(caster.experience, 0);
+        assert_eq!(caster.modifiers.tile_level, 0);
+        assert_eq!(caster.modifiers.tile_column, 2);
+        assert_eq!(caster.prestige, 1);
+        assert_eq!(caster.skill_points, 0);
+        assert_eq!(caster.skills, SkillAllocation::default());
+
+        reset_caster_for_prestige(&mut caster);
+
+        assert_eq!(caster.prestige, 2);
+    }
+
+    #[test]
+    fn test_is_valid_caster_name() {
+        assert!(is_valid_caster_name("Gandalf"));
+        assert!(is_valid_caster_name("Merlin the 2nd"));
+        assert!(is_valid_caster_name("o'Brien_-."));
+        assert!(is_valid_caster_name("abcdefghijklmnopqrstuvwxyz012345"));
+    }
+
+    #[test]
+    fn test_is_valid_caster_name_invalid() {
+        assert!(!is_valid_caster_name(""));
+        assert!(!is_valid_caster_name(" Gandalf"));
+        assert!(!is_valid_caster_name("Gandalf "));
+        assert!(!is_valid_caster_name("Gand:alf"));
+        assert!(!is_valid_caster_name("Gandalf\n"));
+        assert!(!is_valid_caster_name("Gandälf"));
+        assert!(!is_valid_caster_name("abcdefghijklmnopqrstuvwxyz0123456"));
+    }
+
+    #[test]
+    fn test_get_caster_name() {
+        let mut caster = create_caster_for_testing();
+
+        assert_eq!(get_caster_name(&caster), None);
+
+        caster.name = get_caster_name_bytes("Gandalf");
+
+        assert_eq!(get_caster_name(&caster).unwrap(), "Gandalf");
+
+        caster.name = get_caster_name_bytes("abcdefghijklmnopqrstuvwxyz012345");
+
+        assert_eq!(get_caster_name(&caster).unwrap(), "abcdefghijklmnopqrstuvwxyz012345");
+    }
+
+    #[test]
+    fn test_get_cancel_action_refund() {
+        assert_eq!(get_cancel_action_refund(0, 50), 0);
+        assert_eq!(get_cancel_action_refund(10, 50), 5);
+        assert_eq!(get_cancel_action_refund(15, 50), 7);
+        assert_eq!(get_cancel_action_refund(15, 0), 0);
+        assert_eq!(get_cancel_action_refund(15, 100), 15);
+        assert_eq!(get_cancel_action_refund(u64::MAX, 100), u64::MAX);
+    }
+
+    #[test]
+    fn test_allocate_skill_points() {
+        let mut caster = create_caster_for_testing();
+        caster.skill_points = 5;
+
+        allocate_skill_points(&mut caster, SkillType::Fire, 3).unwrap();
+        allocate_skill_points(&mut caster, SkillType::CraftingLuck, 2).unwrap();
+
+        assert_eq!(caster.skill_points, 0);
+        assert_eq!(caster.skills.fire, 3);
+        assert_eq!(caster.skills.crafting_luck, 2);
+    }
+
+    #[test]
+    fn test_allocate_skill_points_not_enough_points() {
+        let mut caster = create_caster_for_testing();
+        caster.skill_points = 2;
+
+        assert!(allocate_skill_points(&mut caster, SkillType::Water, 3).is_err());
+        assert!(allocate_skill_points(&mut caster, SkillType::Water, 0).is_err());
+        assert_eq!(caster.skill_points, 2);
+        assert_eq!(caster.skills.water, 0);
+    }
+
+    #[test]
+    fn test_reset_caster_skills() {
+        let mut caster = create_caster_for_testing();
+        caster.skill_points = 1;
+        caster.skills.earth = 4;
+        caster.skills.magic_find = 2;
+
+        reset_caster_skills(&mut caster);
+
+        assert_eq!(caster.skill_points, 7);
+        assert_eq!(caster.skills, SkillAllocation::default());
+    }
+
+    #[test]
+    fn test_migrate_legacy_caster() {
+        let head = Pubkey::new_unique();
+        let legacy_caster = LegacyCaster {
+            version: 1,
+            level: 12,
+            experience: 700_000,
+            owner: Pubkey::new_unique(),
+            modifiers: LegacyModifiers {
+                tile_level: 11,
+                tile_column: 2,
+                head: Some(head),
+                ..Default::default()
+            },
+            turn_commit: Some(LegacyTurnCommit {
+                turn: 42,
+                resources_burned: [10, 0, 20],
+                actions: LegacyCommittedActions {
+                    loot: true,
+                    mv: Some([3, 1]),
+                    action_order: [2, 0, 1, 0],
+                    ..Default::default()
+                },
+            }),
+            prestige: 1,
+            name: get_caster_name_bytes("Merlin"),
+            ..Default::default()
+        };
+
+        //Goes through the same serialization as the account data
+        let data = legacy_caster.try_to_vec().unwrap();
+        let caster = migrate_legacy_caster(&LegacyCaster::deserialize(&mut data.as_slice()).unwrap());
+
+        assert_eq!(caster.version, CASTER_VERSION);
+        assert_eq!(caster.level, 12);
+        assert_eq!(caster.experience, 700_000);
+        assert_eq!(caster.owner, legacy_caster.owner);
+        assert_eq!(caster.modifiers.tile_level, 11);
+        assert_eq!(caster.modifiers.head, Some(head));
+        assert_eq!(caster.modifiers.amulet, None);
+        assert_eq!(caster.modifiers.ring, None);
+        assert_eq!(caster.prestige, 1);
+        assert_eq!(get_caster_name(&caster).unwrap(), "Merlin");
+
+        //Pending turns are kept, without any consumable used
+        let turn_commit = caster.turn_commit.unwrap();
+        assert_eq!(turn_commit.turn, 42);
+        assert_eq!(turn_commit.resources_burned, [10, 0, 20]);
+        assert!(turn_commit.actions.loot);
+        assert_eq!(turn_commit.actions.mv, Some([3, 1]));
+        assert_eq!(turn_commit.actions.action_order, [2, 0, 1, 0]);
+        assert!(turn_commit.actions.consumables.is_empty());
+    }
+
+    #[test]
+    fn test_get_lada_reward_for_partial_burns() {
+        //Burning a third of every element gets a third of the LADA
+        assert_eq!(
+            get_lada_reward_for_resources_burned([1, 1, 1], [3, 3, 3], [1, 1, 1], 1_000_000_000).unwrap(),
+            333_333_333
+        );
+        //Elements no one burned aren't given
+        assert_eq!(
+            get_lada_reward_for_resources_burned([5, 0, 0], [10, 0, 0], [1, 1, 1], 900).unwrap(),
+            150
+        );
+        assert_eq!(
+            get_lada_reward_for_resources_burned([0, 0, 0], [10, 10, 10], [1, 1, 1], 900).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_get_lada_reward_with_weights() {
+        assert_eq!(
+            get_lada_reward_for_resources_burned([10, 0, 0], [10, 10, 10], [2, 1, 1], 1_000).unwrap(),
+            500
+        );
+        assert_eq!(
+            get_lada_reward_for_resources_burned([10, 10, 10], [10, 10, 10], [0, 3, 1], 1_000).unwrap(),
+            1_000
+        );
+        assert_eq!(
+            get_lada_reward_for_resources_burned([10, 10, 10], [10, 10, 10], [0, 0, 0], 1_000).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_get_lada_reward_never_exceeds_the_turn() {
+        let total_resources_burned = [7, 11, 13];
+        let casters_resources_burned = [[1, 4, 6], [3, 2, 5], [3, 5, 2]];
+
+        let total_given: u64 = casters_resources_burned
+            .iter()
+            .map(|resources_burned| {
+                get_lada_reward_for_resources_burned(*resources_burned, total_resources_burned, [3, 2, 1], 1_984_126_984_130).unwrap()
+            })
+            .sum();
+
+        assert!(total_given <= 1_984_126_984_130);
+        //Only the rounding dust is left
+        assert!(1_984_126_984_130 - total_given < casters_resources_burned.len() as u64);
+    }
+
+    #[test]
+    fn test_check_caster_transfer() {
+        let player = Pubkey::new_unique();
+        let mut caster = create_caster_for_testing();
+        caster.owner = player;
+        caster.modifiers = Modifiers::default();
+
+        assert!(check_caster_transfer(&caster, player, Pubkey::new_unique()).is_ok());
+    }
+
+    #[test]
+    fn test_check_caster_transfer_not_owner() {
+        let mut caster = create_caster_for_testing();
+        caster.modifiers = Modifiers::default();
+
+        assert_eq!(
+            check_caster_transfer(&caster, Pubkey::new_unique(), Pubkey::new_unique()),
+            Err(ErrorCode::CasterNotOwned.into())
+        );
+    }
+
+    #[test]
+    fn test_check_caster_transfer_same_player() {
+        let player = Pubkey::new_unique();
+        let mut caster = create_caster_for_testing();
+        caster.owner = player;
+        caster.modifiers = Modifiers::default();
+
+        assert_eq!(
+            check_caster_transfer(&caster, player, player),
+            Err(ErrorCode::InvalidCasterTransferSamePlayer.into())
+        );
+    }
+
+    #[test]
+    fn test_check_caster_transfer_pending_turn() {
+        let player = Pubkey::new_unique();
+        let mut caster = create_caster_for_testing();
+        caster.owner = player;
+        caster.modifiers = Modifiers::default();
+        caster.turn_commit = Some(TurnCommit::default());
+
+        assert_eq!(
+            check_caster_transfer(&caster, player, Pubkey::new_unique()),
+            Err(ErrorCode::InvalidCasterTransferPendingTurn.into())
+        );
+    }
+
+    #[test]
+    fn test_check_caster_transfer_equipped() {
+        let player = Pubkey::new_unique();
+        let mut caster = create_caster_for_testing();
+        caster.owner = player;
+
+        assert_eq!(
+            check_caster_transfer(&caster, player, Pubkey::new_unique()),
+            Err(ErrorCode::InvalidCasterTransferEquipped.into())
+        );
+    }
+}
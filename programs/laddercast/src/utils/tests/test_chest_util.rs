@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod test_internal_functions {
+    use crate::account::TurnData;
+    use crate::error::ErrorCode;
+    use crate::utils::{check_chest_batch, get_chest_lada, MAX_CHESTS_PER_BATCH};
+
+    #[test]
+    fn test_get_chest_lada() {
+        let mut turn_data = TurnData {
+            chest_lada_distributed: 40,
+            ..Default::default()
+        };
+
+        assert_eq!(get_chest_lada(10, &turn_data, 100, 1_000), 10);
+        //Limited by what's left of the turn's chest LADA
+        assert_eq!(get_chest_lada(80, &turn_data, 100, 1_000), 60);
+        //Limited by what's left in the game
+        assert_eq!(get_chest_lada(10, &turn_data, 100, 5), 5);
+
+        turn_data.chest_lada_distributed = 100;
+        assert_eq!(get_chest_lada(10, &turn_data, 100, 1_000), 0);
+
+        //The LADA of the casters isn't used by the chests
+        let turn_data = TurnData {
+            lada_to_distribute: 1_000,
+            ..Default::default()
+        };
+        assert_eq!(get_chest_lada(10, &turn_data, 0, 1_000), 0);
+    }
+
+    #[test]
+    fn test_check_chest_batch() {
+        assert!(check_chest_batch(1, 1).is_ok());
+        assert!(check_chest_batch(MAX_CHESTS_PER_BATCH, 20).is_ok());
+
+        assert_eq!(check_chest_batch(0, 3), Err(ErrorCode::ChestAccountMissing.into()));
+        assert_eq!(check_chest_batch(3, 2), Err(ErrorCode::ChestAccountMissing.into()));
+        assert_eq!(
+            check_chest_batch(MAX_CHESTS_PER_BATCH + 1, 40),
+            Err(ErrorCode::TooManyChestsInBatch.into())
+        );
+    }
+}
@@ -0,0 +1,316 @@
+#[cfg(test)]
+mod test_internal_functions {
+    use anchor_lang::prelude::Pubkey;
+
+    use crate::account::{GameConfig, TurnData};
+    use crate::utils::{
+        get_default_loot_tables, EmissionSchedule, ExperienceCurve, ExperienceCurveMode,
+        ItemFeature, ItemRarity, LootDrop, LootTable, LootWeights, SetBonuses,
+        EXPERIENCE_REQUIRED_PER_LEVEL, LADA_DISTRIBUTION_PER_TURN, MAX_CHEST_ITEMS,
+    };
+
+    #[test]
+    fn test_get_experience_required_table() {
+        let experience_curve = ExperienceCurve::default();
+
+        assert_eq!(experience_curve.level_cap, 30);
+        assert_eq!(experience_curve.get_experience_required(1), EXPERIENCE_REQUIRED_PER_LEVEL[0]);
+        assert_eq!(experience_curve.get_experience_required(29), EXPERIENCE_REQUIRED_PER_LEVEL[28]);
+        //Out of the table, can't level up anymore
+        assert_eq!(experience_curve.get_experience_required(31), u64::MAX);
+    }
+
+    #[test]
+    fn test_get_experience_required_formula() {
+        let experience_curve = ExperienceCurve {
+            level_cap: 10,
+            mode: ExperienceCurveMode::Formula {
+                multiplier: 1500,
+                exponent: 2,
+            },
+        };
+
+        assert_eq!(experience_curve.get_experience_required(1), 1500);
+        assert_eq!(experience_curve.get_experience_required(3), 13500);
+    }
+
+    #[test]
+    fn test_get_min_experience_for_level() {
+        let experience_curve = ExperienceCurve::default();
+
+        assert_eq!(experience_curve.get_min_experience_for_level(1), 0);
+        assert_eq!(experience_curve.get_min_experience_for_level(2), EXPERIENCE_REQUIRED_PER_LEVEL[0]);
+        assert_eq!(experience_curve.get_min_experience_for_level(30), EXPERIENCE_REQUIRED_PER_LEVEL[28]);
+    }
+
+    #[test]
+    fn test_is_valid() {
+        assert!(ExperienceCurve::default().is_valid());
+
+        assert!(ExperienceCurve {
+            level_cap: 3,
+            mode: ExperienceCurveMode::Table {
+                experience_required_per_level: vec![100, 200],
+            },
+        }
+            .is_valid());
+    }
+
+    #[test]
+    fn test_is_valid_invalid_level_cap() {
+        let mut experience_curve = ExperienceCurve::default();
+
+        experience_curve.level_cap = 0;
+        assert!(!experience_curve.is_valid());
+
+        experience_curve.level_cap = 31;
+        assert!(!experience_curve.is_valid());
+    }
+
+    #[test]
+    fn test_is_valid_invalid_table() {
+        //Missing the entry to go from level 3 to 4
+        assert!(!ExperienceCurve {
+            level_cap: 4,
+            mode: ExperienceCurveMode::Table {
+                experience_required_per_level: vec![100, 200],
+            },
+        }
+            .is_valid());
+
+        //Can't require less experience for a higher level
+        assert!(!ExperienceCurve {
+            level_cap: 3,
+            mode: ExperienceCurveMode::Table {
+                experience_required_per_level: vec![200, 100],
+            },
+        }
+            .is_valid());
+    }
+
+    #[test]
+    fn test_is_valid_invalid_formula() {
+        assert!(!ExperienceCurve {
+            level_cap: 30,
+            mode: ExperienceCurveMode::Formula {
+                multiplier: 0,
+                exponent: 2,
+            },
+        }
+            .is_valid());
+    }
+
+    #[test]
+    fn test_is_valid_invalid_formula_exponent() {
+        assert!(!ExperienceCurve {
+            level_cap: 30,
+            mode: ExperienceCurveMode::Formula {
+                multiplier: 100,
+                exponent: 0,
+            },
+        }
+            .is_valid());
+    }
+
+    #[test]
+    fn test_get_set_bonus_percent() {
+        let set_bonuses = SetBonuses::default();
+
+        //Same feature and same rarity
+        assert_eq!(
+            set_bonuses.get_bonus_percent(&[
+                (ItemFeature::Fire, ItemRarity::Epic),
+                (ItemFeature::Fire, ItemRarity::Epic),
+                (ItemFeature::Fire, ItemRarity::Epic),
+            ]),
+            35
+        );
+
+        //Same rarity only
+        assert_eq!(
+            set_bonuses.get_bonus_percent(&[
+                (ItemFeature::Fire, ItemRarity::Legendary),
+                (ItemFeature::Water, ItemRarity::Legendary),
+                (ItemFeature::Magic, ItemRarity::Legendary),
+            ]),
+            25
+        );
+
+        //Nothing in common
+        assert_eq!(
+            set_bonuses.get_bonus_percent(&[
+                (ItemFeature::Fire, ItemRarity::Common),
+                (ItemFeature::Water, ItemRarity::Rare),
+                (ItemFeature::Magic, ItemRarity::Common),
+            ]),
+            0
+        );
+
+        //3 matching pieces out of 5 is still a set
+        assert_eq!(
+            set_bonuses.get_bonus_percent(&[
+                (ItemFeature::Fire, ItemRarity::Common),
+                (ItemFeature::Water, ItemRarity::Rare),
+                (ItemFeature::Fire, ItemRarity::Epic),
+                (ItemFeature::Power, ItemRarity::Legendary),
+                (ItemFeature::Fire, ItemRarity::Common),
+            ]),
+            20
+        );
+
+        //Needs at least three pieces
+        assert_eq!(
+            set_bonuses.get_bonus_percent(&[
+                (ItemFeature::Fire, ItemRarity::Common),
+                (ItemFeature::Fire, ItemRarity::Common),
+            ]),
+            0
+        );
+    }
+
+    #[test]
+    fn test_loot_weights_get_drop() {
+        let weights = LootWeights {
+            spell_book: 2,
+            equipment: 3,
+            consumable: 0,
+            resources: 1,
+            lada: 4,
+        };
+
+        assert_eq!(weights.get_total_weight(), 10);
+        assert_eq!(weights.get_drop(1), LootDrop::SpellBook);
+        assert_eq!(weights.get_drop(2), LootDrop::SpellBook);
+        assert_eq!(weights.get_drop(3), LootDrop::Equipment);
+        assert_eq!(weights.get_drop(5), LootDrop::Equipment);
+        //No weight, never dropped
+        assert_eq!(weights.get_drop(6), LootDrop::Resources);
+        assert_eq!(weights.get_drop(7), LootDrop::Lada);
+        assert_eq!(weights.get_drop(10), LootDrop::Lada);
+    }
+
+    #[test]
+    fn test_loot_table_is_valid() {
+        for loot_table in get_default_loot_tables().iter() {
+            assert!(loot_table.is_valid());
+            assert!(!loot_table.is_empty());
+        }
+
+        let loot_table = LootTable::get_default_for_tier(1);
+
+        assert!(LootTable::default().is_empty());
+        assert!(!LootTable::default().is_valid());
+        assert!(!LootTable { min_items: 4, max_items: 3, ..loot_table }.is_valid());
+        assert!(!LootTable { max_items: MAX_CHEST_ITEMS + 1, ..loot_table }.is_valid());
+        assert!(!LootTable { weights: LootWeights::default(), ..loot_table }.is_valid());
+        assert!(!LootTable { min_item_level: 0, ..loot_table }.is_valid());
+    }
+
+    #[test]
+    fn test_default_loot_tables_follow_tiers() {
+        let loot_tables = get_default_loot_tables();
+
+        assert_eq!(loot_tables[0].min_item_level, 1);
+        assert_eq!(loot_tables[1].min_item_level, 6);
+        assert_eq!(loot_tables[2].min_item_level, 11);
+        assert_eq!(loot_tables[3].min_item_level, 16);
+
+        //Same content as the chests from before loot tables
+        for loot_table in loot_tables.iter() {
+            assert_eq!(loot_table.min_items, 3);
+            assert_eq!(loot_table.max_items, 3);
+            assert_eq!(loot_table.weights.get_total_weight(), 4);
+            assert_eq!(loot_table.weights.get_drop(1), LootDrop::SpellBook);
+            assert_eq!(loot_table.weights.get_drop(2), LootDrop::Equipment);
+            assert_eq!(loot_table.weights.get_drop(4), LootDrop::Equipment);
+            assert_eq!(loot_table.rarity_floor, ItemRarity::Common);
+        }
+    }
+
+    #[test]
+    fn test_emission_schedule_default() {
+        let emission_schedule = EmissionSchedule::default();
+
+        assert_eq!(emission_schedule.get_lada_for_turn(1), LADA_DISTRIBUTION_PER_TURN);
+        assert_eq!(emission_schedule.get_lada_for_turn(10_000), LADA_DISTRIBUTION_PER_TURN);
+    }
+
+    #[test]
+    fn test_emission_schedule_step_halving() {
+        let emission_schedule = EmissionSchedule::StepHalving { initial_amount: 1_000, start_turn: 10, halving_period: 5 };
+
+        assert_eq!(emission_schedule.get_lada_for_turn(1), 1_000);
+        assert_eq!(emission_schedule.get_lada_for_turn(14), 1_000);
+        assert_eq!(emission_schedule.get_lada_for_turn(15), 500);
+        assert_eq!(emission_schedule.get_lada_for_turn(20), 250);
+        assert_eq!(emission_schedule.get_lada_for_turn(u32::MAX), 0);
+        assert!(!EmissionSchedule::StepHalving { initial_amount: 1_000, start_turn: 10, halving_period: 0 }.is_valid());
+    }
+
+    #[test]
+    fn test_emission_schedule_linear_decay() {
+        let emission_schedule = EmissionSchedule::LinearDecay { initial_amount: 1_000, start_turn: 1, decay_turns: 4 };
+
+        assert_eq!(emission_schedule.get_lada_for_turn(1), 1_000);
+        assert_eq!(emission_schedule.get_lada_for_turn(2), 750);
+        assert_eq!(emission_schedule.get_lada_for_turn(4), 250);
+        assert_eq!(emission_schedule.get_lada_for_turn(5), 0);
+        assert_eq!(emission_schedule.get_lada_for_turn(100), 0);
+    }
+
+    #[test]
+    fn test_emission_schedule_season_budget() {
+        let emission_schedule = EmissionSchedule::SeasonBudget { budget: 1_000, start_turn: 1, season_turns: 3 };
+
+        let total: u64 = (1..=10).map(|turn| emission_schedule.get_lada_for_turn(turn)).sum();
+
+        assert_eq!(emission_schedule.get_lada_for_turn(1), 333);
+        assert_eq!(emission_schedule.get_lada_for_turn(3), 334);
+        assert_eq!(emission_schedule.get_lada_for_turn(4), 0);
+        assert_eq!(total, 1_000);
+    }
+
+    #[test]
+    fn test_emission_schedule_season_budget_not_started() {
+        let emission_schedule = EmissionSchedule::SeasonBudget { budget: 1_000, start_turn: 5, season_turns: 2 };
+
+        let total: u64 = (1..=10).map(|turn| emission_schedule.get_lada_for_turn(turn)).sum();
+
+        //Nothing is given before the season starts
+        assert_eq!(emission_schedule.get_lada_for_turn(1), 0);
+        assert_eq!(emission_schedule.get_lada_for_turn(4), 0);
+        assert_eq!(emission_schedule.get_lada_for_turn(5), 500);
+        assert_eq!(emission_schedule.get_lada_for_turn(6), 500);
+        assert_eq!(emission_schedule.get_lada_for_turn(7), 0);
+        assert_eq!(total, 1_000);
+    }
+
+    #[test]
+    fn test_game_config_emission_schedule() {
+        let mut game_config = GameConfig::new(Pubkey::new_unique(), 0);
+
+        assert_eq!(game_config.get_emission_schedule(), EmissionSchedule::default());
+
+        //A schedule without emissions is kept
+        game_config.emission_schedule = Some(EmissionSchedule::Flat { amount_per_turn: 0 });
+
+        assert_eq!(game_config.get_emission_schedule(), EmissionSchedule::Flat { amount_per_turn: 0 });
+        assert_eq!(game_config.get_emission_schedule().get_lada_for_turn(1), 0);
+    }
+
+    #[test]
+    fn test_turn_data_get_lada_to_distribute() {
+        let emission_schedule = EmissionSchedule::Flat { amount_per_turn: 1_000 };
+        let mut turn_data = TurnData::default();
+
+        //Turns started before the schedule existed use the schedule
+        assert_eq!(turn_data.get_lada_to_distribute(&emission_schedule, 3), 1_000);
+
+        turn_data.set_lada_to_distribute(400);
+        assert_eq!(turn_data.get_lada_to_distribute(&emission_schedule, 3), 400);
+
+        //Turns started while the game's LADA ran out keep giving nothing
+        turn_data.set_lada_to_distribute(0);
+        assert_eq!(turn_data.get_lada_to_distribute(&emission_schedule, 3), 0);
+    }
+}
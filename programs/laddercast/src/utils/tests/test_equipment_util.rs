@@ -2,11 +2,25 @@
 mod test_internal_functions {
     use std::convert::TryInto;
 
+    use anchor_lang::prelude::{Account, AccountInfo, Pubkey};
+    use anchor_lang::AccountSerialize;
     use lazy_static::lazy_static;
     use rand::random;
 
     use crate::{ItemFeature, ItemType};
-    use crate::utils::{create_game_for_testing, generate_new_equipment, generate_new_spell_book, get_item_percentage_value, get_item_rarity, get_item_resource_value, ItemRarity, SpellType};
+    use crate::account::Item;
+    use crate::error::ErrorCode;
+    use crate::utils::{
+        check_equipment_accounts, create_caster_modifiers_for_testing, create_chest_for_testing,
+        create_equipment_for_testing, create_game_for_testing, create_spell_book_for_testing,
+        create_zombie_for_testing, equip_item_to_caster, generate_new_equipment,
+        generate_new_spell_book, generate_secondary_stat, get_item_durability,
+        get_item_percentage_value, get_item_rarity, get_item_resource_value, get_loadout_slot,
+        get_reforge_cost, get_repair_cost, get_resources_for_feature, get_salvage_resources,
+        get_spell_book_charges, get_spell_book_charges_left, is_item_broken, reforge_equipment,
+        set_caster_loadout, unequip_item_from_caster, wear_item, EquipmentType, ItemRarity,
+        Modifiers, SpellType, TurnCommit,
+    };
 
     lazy_static! {
         static ref SLOT_HASHES: [u8; 512 * 40] = generate_slot_hashes(true).try_into().unwrap();
@@ -69,4 +83,438 @@ mod test_internal_functions {
             ItemRarity::Common | ItemRarity::Rare | ItemRarity::Epic | ItemRarity::Legendary
         ));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_get_salvage_resources() {
+        let chest = create_chest_for_testing();
+        assert_eq!(get_salvage_resources(&chest.item_type, chest.level), Some([15, 15, 15]));
+
+        let spell_book = create_spell_book_for_testing();
+        assert_eq!(get_salvage_resources(&spell_book.item_type, spell_book.level), Some([15, 0, 0]));
+
+        let mut equipment = create_equipment_for_testing(EquipmentType::Head);
+        assert_eq!(get_salvage_resources(&equipment.item_type, equipment.level), Some([5, 5, 5]));
+
+        equipment.item_type = ItemType::Equipment {
+            feature: ItemFeature::Earth,
+            rarity: ItemRarity::Legendary,
+            equipment_type: EquipmentType::Head,
+            value: 1,
+        };
+        assert_eq!(get_salvage_resources(&equipment.item_type, equipment.level), Some([0, 0, 120]));
+
+        let zombie = create_zombie_for_testing();
+        assert_eq!(get_salvage_resources(&zombie.item_type, zombie.level), None);
+    }
+
+    #[test]
+    fn test_get_resources_for_feature() {
+        assert_eq!(get_resources_for_feature(ItemFeature::Water, 16), [0, 16, 0]);
+        assert_eq!(get_resources_for_feature(ItemFeature::Power, 15), [5, 5, 5]);
+        //Nothing is lost when the amount doesn't split evenly
+        assert_eq!(get_resources_for_feature(ItemFeature::Magic, 16), [6, 5, 5]);
+        assert_eq!(get_resources_for_feature(ItemFeature::Power, 2), [2, 0, 0]);
+    }
+
+    #[test]
+    fn test_get_reforge_cost() {
+        let mut equipment = create_equipment_for_testing(EquipmentType::Staff);
+        equipment.item_type = ItemType::Equipment {
+            feature: ItemFeature::Water,
+            rarity: ItemRarity::Rare,
+            equipment_type: EquipmentType::Staff,
+            value: 1,
+        };
+
+        assert_eq!(get_reforge_cost(&equipment.item_type, 3, 0), Some([0, 30, 0]));
+        assert_eq!(get_reforge_cost(&equipment.item_type, 3, 2), Some([0, 120, 0]));
+
+        let spell_book = create_spell_book_for_testing();
+        assert_eq!(get_reforge_cost(&spell_book.item_type, 3, 0), None);
+    }
+
+    #[test]
+    fn test_reforge_equipment() {
+        let mut offset: usize = 1;
+        let mut equipment = create_equipment_for_testing(EquipmentType::Robe);
+
+        for _i in 0..4 {
+            reforge_equipment(&mut equipment, true, SLOT_HASHES.as_slice(), &mut offset).unwrap();
+
+            if let ItemType::Equipment { value, .. } = equipment.item_type {
+                //Level 3 common percentage value is between 100 and 200
+                assert!((100..=200).contains(&value));
+            }
+        }
+
+        assert_eq!(equipment.reforge_count, 4);
+
+        let mut spell_book = create_spell_book_for_testing();
+        assert!(reforge_equipment(&mut spell_book, false, SLOT_HASHES.as_slice(), &mut offset).is_err());
+        assert_eq!(spell_book.reforge_count, 0);
+    }
+
+    #[test]
+    fn test_wear_item() {
+        let mut equipment = create_equipment_for_testing(EquipmentType::Head);
+        equipment.wear = 98;
+
+        assert_eq!(get_item_durability(&equipment), 2);
+
+        wear_item(&mut equipment);
+        assert!(!is_item_broken(&equipment));
+
+        wear_item(&mut equipment);
+        wear_item(&mut equipment);
+
+        //Common items break after 100 uses, wear stops there
+        assert!(is_item_broken(&equipment));
+        assert_eq!(equipment.wear, 100);
+    }
+
+    #[test]
+    fn test_get_repair_cost() {
+        let mut equipment = create_equipment_for_testing(EquipmentType::Head);
+
+        assert_eq!(get_repair_cost(&equipment), None);
+
+        equipment.wear = 10;
+        assert_eq!(get_repair_cost(&equipment), Some([10, 10, 10]));
+
+        let mut spell_book = create_spell_book_for_testing();
+        spell_book.wear = 10;
+        assert_eq!(get_repair_cost(&spell_book), None);
+    }
+
+    #[test]
+    fn test_generate_secondary_stat() {
+        let mut offset: usize = 1;
+
+        assert_eq!(
+            generate_secondary_stat(ItemFeature::Fire, ItemRarity::Rare, 10, SLOT_HASHES.as_slice(), &mut offset),
+            None
+        );
+
+        //Multiple tests to make sure it works with different values (random)
+        for _i in 0..8 {
+            let secondary_stat = generate_secondary_stat(
+                ItemFeature::Fire,
+                ItemRarity::Legendary,
+                10,
+                SLOT_HASHES.as_slice(),
+                &mut offset,
+            )
+            .unwrap();
+
+            assert_ne!(secondary_stat.feature, ItemFeature::Fire);
+
+            match secondary_stat.feature {
+                //Half of the legendary resource value range (301-400)
+                ItemFeature::Water | ItemFeature::Earth => {
+                    assert!((150..=200).contains(&secondary_stat.value))
+                }
+                //Half of the legendary percentage value range (400-700)
+                _ => assert!((200..=350).contains(&secondary_stat.value)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_spell_book_charges() {
+        let mut offset: usize = 1;
+
+        //Multiple tests to make sure it works with different values (random)
+        for _i in 0..4 {
+            assert_eq!(get_spell_book_charges(ItemRarity::Common, SLOT_HASHES.as_slice(), &mut offset), 1);
+
+            let charges = get_spell_book_charges(ItemRarity::Legendary, SLOT_HASHES.as_slice(), &mut offset);
+            assert!((3..=5).contains(&charges));
+        }
+    }
+
+    #[test]
+    fn test_get_spell_book_charges_left() {
+        let mut spell_book = create_spell_book_for_testing();
+
+        //Spell books created before charges existed keep their single cast
+        spell_book.spell_book_charges = 0;
+        assert_eq!(get_spell_book_charges_left(&spell_book), 1);
+
+        spell_book.spell_book_charges = 4;
+        assert_eq!(get_spell_book_charges_left(&spell_book), 4);
+    }
+
+    #[test]
+    fn test_get_loadout_slot() {
+        assert_eq!(get_loadout_slot(&create_equipment_for_testing(EquipmentType::Head).item_type), Some(0));
+        assert_eq!(get_loadout_slot(&create_equipment_for_testing(EquipmentType::Ring).item_type), Some(4));
+        assert_eq!(get_loadout_slot(&create_spell_book_for_testing().item_type), Some(5));
+        assert_eq!(get_loadout_slot(&create_chest_for_testing().item_type), None);
+        assert_eq!(get_loadout_slot(&create_zombie_for_testing().item_type), None);
+    }
+
+    #[test]
+    fn test_check_equipment_accounts() {
+        let modifiers = create_caster_modifiers_for_testing(false);
+        let placeholder = Pubkey::new_unique();
+
+        assert!(check_equipment_accounts(
+            &modifiers,
+            [
+                modifiers.staff.unwrap(),
+                modifiers.head.unwrap(),
+                modifiers.robe.unwrap(),
+                modifiers.amulet.unwrap(),
+                modifiers.ring.unwrap(),
+            ],
+        )
+        .is_ok());
+
+        //Empty slots take any placeholder
+        assert!(check_equipment_accounts(&Modifiers::default(), [placeholder; 5]).is_ok());
+    }
+
+    #[test]
+    fn test_check_equipment_accounts_missing() {
+        let modifiers = create_caster_modifiers_for_testing(false);
+        let placeholder = Pubkey::new_unique();
+
+        //Ring left out
+        assert!(check_equipment_accounts(
+            &modifiers,
+            [
+                modifiers.staff.unwrap(),
+                modifiers.head.unwrap(),
+                modifiers.robe.unwrap(),
+                modifiers.amulet.unwrap(),
+                placeholder,
+            ],
+        )
+        .is_err());
+        //Right items in the wrong slots
+        assert!(check_equipment_accounts(
+            &modifiers,
+            [
+                modifiers.head.unwrap(),
+                modifiers.staff.unwrap(),
+                modifiers.robe.unwrap(),
+                modifiers.amulet.unwrap(),
+                modifiers.ring.unwrap(),
+            ],
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_equip_item_to_caster() {
+        let caster_key = Pubkey::new_unique();
+        let mut caster = create_caster_for_testing();
+        caster.level = 3;
+        caster.modifiers = Modifiers::default();
+
+        let head_key = Pubkey::new_unique();
+        let mut head = create_equipment_for_testing(EquipmentType::Head);
+
+        equip_item_to_caster(&mut caster, caster_key, &mut head, head_key).unwrap();
+
+        assert_eq!(caster.modifiers.head, Some(head_key));
+        assert_eq!(head.equipped_owner, Some(caster_key));
+
+        assert_eq!(
+            equip_item_to_caster(&mut caster, caster_key, &mut head, head_key),
+            Err(ErrorCode::ItemAlreadyInUse.into())
+        );
+
+        let mut other_head = create_equipment_for_testing(EquipmentType::Head);
+        assert_eq!(
+            equip_item_to_caster(&mut caster, caster_key, &mut other_head, Pubkey::new_unique()),
+            Err(ErrorCode::ItemTypeAlreadyEquipped.into())
+        );
+
+        let mut robe = create_equipment_for_testing(EquipmentType::Robe);
+        robe.level = 4;
+        assert_eq!(
+            equip_item_to_caster(&mut caster, caster_key, &mut robe, Pubkey::new_unique()),
+            Err(ErrorCode::ItemLevelTooHigh.into())
+        );
+
+        let mut chest = create_chest_for_testing();
+        chest.level = 1;
+        assert_eq!(
+            equip_item_to_caster(&mut caster, caster_key, &mut chest, Pubkey::new_unique()),
+            Err(ErrorCode::InvalidEquipItemType.into())
+        );
+
+        let mut spell_book = create_spell_book_for_testing();
+        caster.turn_commit = Some(TurnCommit::default());
+        assert_eq!(
+            equip_item_to_caster(&mut caster, caster_key, &mut spell_book, Pubkey::new_unique()),
+            Err(ErrorCode::NoEquipUnequipOnPendingTurn.into())
+        );
+    }
+
+    #[test]
+    fn test_unequip_item_from_caster() {
+        let caster_key = Pubkey::new_unique();
+        let mut caster = create_caster_for_testing();
+        caster.modifiers = Modifiers::default();
+
+        let ring_key = Pubkey::new_unique();
+        let mut ring = create_equipment_for_testing(EquipmentType::Ring);
+        ring.equipped_owner = Some(caster_key);
+        caster.modifiers.ring = Some(ring_key);
+
+        caster.turn_commit = Some(TurnCommit::default());
+        assert_eq!(
+            unequip_item_from_caster(&mut caster, caster_key, &mut ring),
+            Err(ErrorCode::NoEquipUnequipOnPendingTurn.into())
+        );
+
+        caster.turn_commit = None;
+        unequip_item_from_caster(&mut caster, caster_key, &mut ring).unwrap();
+
+        assert_eq!(caster.modifiers.ring, None);
+        assert_eq!(ring.equipped_owner, None);
+
+        //Not equipped by this caster
+        assert_eq!(
+            unequip_item_from_caster(&mut caster, caster_key, &mut ring),
+            Err(ErrorCode::ItemNotExists.into())
+        );
+    }
+
+    struct TestItemAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    impl TestItemAccount {
+        fn new(item: &Item) -> Self {
+            let mut data = vec![0; Item::SIZE];
+            item.try_serialize(&mut data.as_mut_slice()).unwrap();
+
+            Self { key: Pubkey::new_unique(), owner: crate::ID, lamports: 1_000, data }
+        }
+
+        //Empty slot marker
+        fn empty() -> Self {
+            Self { key: Pubkey::default(), owner: Pubkey::default(), lamports: 1, data: vec![] }
+        }
+
+        fn info(&mut self) -> AccountInfo {
+            AccountInfo::new(&self.key, false, true, &mut self.lamports, &mut self.data, &self.owner, false, 0)
+        }
+    }
+
+    fn create_loadout_item_for_testing(equipment_type: EquipmentType, game: Pubkey, player: Pubkey) -> Item {
+        let mut item = create_equipment_for_testing(equipment_type);
+        item.game = game;
+        item.owner = player;
+        item.level = 1;
+        item
+    }
+
+    #[test]
+    fn test_set_caster_loadout() {
+        let (caster_key, game, player) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut caster = create_caster_for_testing();
+        caster.modifiers = Modifiers::default();
+
+        let mut old_head = create_loadout_item_for_testing(EquipmentType::Head, game, player);
+        old_head.equipped_owner = Some(caster_key);
+        let mut old_head_account = TestItemAccount::new(&old_head);
+        caster.modifiers.head = Some(old_head_account.key);
+
+        let mut staff_account = TestItemAccount::new(&create_loadout_item_for_testing(EquipmentType::Staff, game, player));
+        let mut robe_account = TestItemAccount::new(&create_loadout_item_for_testing(EquipmentType::Robe, game, player));
+        let mut empty_accounts: Vec<TestItemAccount> = (0..4).map(|_| TestItemAccount::empty()).collect();
+        let (staff_key, robe_key) = (staff_account.key, robe_account.key);
+
+        let empty_infos: Vec<AccountInfo> = empty_accounts.iter_mut().map(|account| account.info()).collect();
+        let new_item_accounts = [
+            empty_infos[0].clone(),
+            robe_account.info(),
+            staff_account.info(),
+            empty_infos[1].clone(),
+            empty_infos[2].clone(),
+            empty_infos[3].clone(),
+        ];
+        let old_head_info = old_head_account.info();
+
+        set_caster_loadout(&mut caster, caster_key, game, player, &new_item_accounts, &[old_head_info.clone()], &crate::ID).unwrap();
+
+        assert_eq!(caster.modifiers.get_loadout(), [None, Some(robe_key), Some(staff_key), None, None, None]);
+
+        //The items are written back
+        assert_eq!(Account::<Item>::try_from(&old_head_info).unwrap().equipped_owner, None);
+        assert_eq!(Account::<Item>::try_from(&new_item_accounts[1]).unwrap().equipped_owner, Some(caster_key));
+        assert_eq!(Account::<Item>::try_from(&new_item_accounts[2]).unwrap().equipped_owner, Some(caster_key));
+
+        //Keeping an item in its slot unequips and equips it again
+        set_caster_loadout(&mut caster, caster_key, game, player, &new_item_accounts, &[], &crate::ID).unwrap();
+
+        assert_eq!(caster.modifiers.get_loadout(), [None, Some(robe_key), Some(staff_key), None, None, None]);
+    }
+
+    #[test]
+    fn test_set_caster_loadout_invalid() {
+        let (caster_key, game, player) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut caster = create_caster_for_testing();
+        caster.modifiers = Modifiers::default();
+
+        let mut head_account = TestItemAccount::new(&create_loadout_item_for_testing(EquipmentType::Head, game, player));
+        let mut not_an_item_account = TestItemAccount::empty();
+        not_an_item_account.key = Pubkey::new_unique();
+        let mut empty_accounts: Vec<TestItemAccount> = (0..5).map(|_| TestItemAccount::empty()).collect();
+        let empty_infos: Vec<AccountInfo> = empty_accounts.iter_mut().map(|account| account.info()).collect();
+        let head_info = head_account.info();
+
+        //Only the default pubkey leaves a slot empty, anything else has to be an item
+        let new_item_accounts = [
+            not_an_item_account.info(),
+            empty_infos[0].clone(),
+            empty_infos[1].clone(),
+            empty_infos[2].clone(),
+            empty_infos[3].clone(),
+            empty_infos[4].clone(),
+        ];
+        assert!(set_caster_loadout(&mut caster, caster_key, game, player, &new_item_accounts, &[], &crate::ID).is_err());
+
+        //Item in the wrong slot
+        let new_item_accounts = [
+            empty_infos[0].clone(),
+            head_info.clone(),
+            empty_infos[1].clone(),
+            empty_infos[2].clone(),
+            empty_infos[3].clone(),
+            empty_infos[4].clone(),
+        ];
+        assert_eq!(
+            set_caster_loadout(&mut caster, caster_key, game, player, &new_item_accounts, &[], &crate::ID),
+            Err(ErrorCode::InvalidEquipItemType.into())
+        );
+
+        //Item of another player
+        let new_item_accounts = [
+            head_info.clone(),
+            empty_infos[0].clone(),
+            empty_infos[1].clone(),
+            empty_infos[2].clone(),
+            empty_infos[3].clone(),
+            empty_infos[4].clone(),
+        ];
+        assert_eq!(
+            set_caster_loadout(&mut caster, caster_key, game, Pubkey::new_unique(), &new_item_accounts, &[], &crate::ID),
+            Err(ErrorCode::ItemNotOwned.into())
+        );
+
+        //Currently equipped item not provided
+        caster.modifiers.ring = Some(Pubkey::new_unique());
+        assert_eq!(
+            set_caster_loadout(&mut caster, caster_key, game, player, &new_item_accounts, &[], &crate::ID),
+            Err(ErrorCode::LoadoutItemMissing.into())
+        );
+    }
+}
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod test_internal_functions {
+    use anchor_lang::prelude::Pubkey;
+
+    use crate::account::Inventory;
+    use crate::utils::{add_inventory_item, remove_inventory_item, MAX_INVENTORY_ITEMS};
+
+    #[test]
+    fn test_inventory_add_and_remove() {
+        let mut inventory = Inventory {
+            bump: 0,
+            player: Pubkey::new_unique(),
+            items: vec![],
+        };
+        let item_1 = Pubkey::new_unique();
+        let item_2 = Pubkey::new_unique();
+
+        assert!(add_inventory_item(&mut inventory, item_1));
+        assert!(add_inventory_item(&mut inventory, item_2));
+        //Already indexed
+        assert!(add_inventory_item(&mut inventory, item_1));
+
+        assert_eq!(inventory.items, vec![item_1, item_2]);
+
+        remove_inventory_item(&mut inventory, &item_1);
+        //Not indexed
+        remove_inventory_item(&mut inventory, &Pubkey::new_unique());
+
+        assert_eq!(inventory.items, vec![item_2]);
+    }
+
+    #[test]
+    fn test_inventory_full() {
+        let mut inventory = Inventory {
+            bump: 0,
+            player: Pubkey::new_unique(),
+            items: (0..MAX_INVENTORY_ITEMS).map(|_| Pubkey::new_unique()).collect(),
+        };
+
+        //New items aren't indexed anymore, but it doesn't fail the action creating them
+        assert!(!add_inventory_item(&mut inventory, Pubkey::new_unique()));
+        assert_eq!(inventory.items.len(), MAX_INVENTORY_ITEMS);
+
+        //Items already indexed are still reported as indexed
+        let indexed_item = inventory.items[0];
+        assert!(add_inventory_item(&mut inventory, indexed_item));
+
+        //Burned items free up room
+        remove_inventory_item(&mut inventory, &indexed_item);
+        let new_item = Pubkey::new_unique();
+        assert!(add_inventory_item(&mut inventory, new_item));
+        assert_eq!(inventory.items.last(), Some(&new_item));
+    }
+}
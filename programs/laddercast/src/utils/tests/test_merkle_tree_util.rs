@@ -1,6 +1,12 @@
 #[cfg(test)]
 mod test_internal_functions {
-    use crate::utils::{create_caster_for_testing, create_caster_modifiers_for_testing, create_chest_for_testing, create_equipment_for_testing, create_spell_book_for_testing, create_zombie_for_testing, EquipmentType, get_merkle_string_for_caster, get_merkle_string_for_item, verify_merkle_proof};
+    use crate::ItemFeature;
+    use crate::utils::{
+        create_caster_for_testing, create_caster_modifiers_for_testing, create_chest_for_testing,
+        create_equipment_for_testing, create_spell_book_for_testing, create_zombie_for_testing,
+        get_caster_name_bytes, get_merkle_string_for_caster, get_merkle_string_for_item,
+        verify_merkle_proof, EquipmentType, SecondaryStat, CASTER_VERSION,
+    };
 
     const URI: &str = "https://laddercaster.com";
 
@@ -50,6 +56,21 @@ mod test_internal_functions {
             "https://laddercaster.com:spellbook:3:fire:fire:common:1:2"
         );
 
+        //Leaves of spell books with the default charges are the same as before charges existed
+        item.spell_book_charges = 1;
+
+        assert_eq!(
+            get_merkle_string_for_item(URI, item).unwrap(),
+            "https://laddercaster.com:spellbook:3:fire:fire:common:1:2"
+        );
+
+        item.spell_book_charges = 3;
+
+        assert_eq!(
+            get_merkle_string_for_item(URI, item).unwrap(),
+            "https://laddercaster.com:spellbook:3:fire:fire:common:1:2:3"
+        );
+
         item = create_equipment_for_testing(EquipmentType::Staff);
 
         assert_eq!(
@@ -72,6 +93,38 @@ mod test_internal_functions {
         );
     }
 
+    #[test]
+    fn test_get_merkle_string_for_item_worn_equipment() {
+        let mut item = create_equipment_for_testing(EquipmentType::Robe);
+        item.wear = 12;
+
+        assert_eq!(
+            get_merkle_string_for_item(URI, item).unwrap(),
+            "https://laddercaster.com:robe:3:power:common:1:12"
+        );
+    }
+
+    #[test]
+    fn test_get_merkle_string_for_item_with_secondary_stat() {
+        let mut item = create_equipment_for_testing(EquipmentType::Ring);
+        item.secondary_stat = Some(SecondaryStat {
+            feature: ItemFeature::Earth,
+            value: 42,
+        });
+
+        assert_eq!(
+            get_merkle_string_for_item(URI, item).unwrap(),
+            "https://laddercaster.com:ring:3:power:common:1:earth:42"
+        );
+
+        item.wear = 3;
+
+        assert_eq!(
+            get_merkle_string_for_item(URI, item).unwrap(),
+            "https://laddercaster.com:ring:3:power:common:1:earth:42:3"
+        );
+    }
+
     #[test]
     fn test_get_merkle_string_for_caster() {
         let caster = create_caster_for_testing();
@@ -82,6 +135,29 @@ mod test_internal_functions {
         );
     }
 
+    #[test]
+    fn test_get_merkle_string_for_caster_migrated() {
+        let mut caster = create_caster_for_testing();
+        caster.version = CASTER_VERSION;
+
+        //Migrating the account doesn't change the leaf of the NFT
+        assert_eq!(
+            get_merkle_string_for_caster(URI, caster),
+            "https://laddercaster.com:caster:1:1"
+        );
+    }
+
+    #[test]
+    fn test_get_merkle_string_for_caster_ignores_name() {
+        let mut caster = create_caster_for_testing();
+        caster.name = get_caster_name_bytes("Gandalf");
+
+        assert_eq!(
+            get_merkle_string_for_caster(URI, caster),
+            "https://laddercaster.com:caster:1:1"
+        );
+    }
+
     #[test]
     fn test_verify_merkle_proof_invalid() {
         let valid_proof = vec![
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod test_internal_functions {
+    use crate::PlayerBonuses;
+    use crate::utils::{
+        apply_prestige_bonuses, apply_set_bonus, apply_skill_bonuses, SkillAllocation,
+    };
+
+    fn create_player_bonuses_for_testing() -> PlayerBonuses {
+        PlayerBonuses {
+            critical_chance: 200,
+            magic_find_chance: 0,
+            fire_chance: 10,
+            water_chance: 0,
+            earth_chance: 0,
+            crafting_chance: 0,
+        }
+    }
+
+    #[test]
+    fn test_apply_prestige_bonuses_no_prestige() {
+        let mut player_bonuses = create_player_bonuses_for_testing();
+
+        apply_prestige_bonuses(&mut player_bonuses, 0);
+
+        assert_eq!(player_bonuses, create_player_bonuses_for_testing());
+    }
+
+    #[test]
+    fn test_apply_prestige_bonuses_with_prestige() {
+        let mut player_bonuses = create_player_bonuses_for_testing();
+
+        apply_prestige_bonuses(&mut player_bonuses, 2);
+
+        assert_eq!(player_bonuses.critical_chance, 250);
+        assert_eq!(player_bonuses.magic_find_chance, 50);
+        assert_eq!(player_bonuses.fire_chance, 14);
+        assert_eq!(player_bonuses.water_chance, 4);
+        assert_eq!(player_bonuses.earth_chance, 4);
+    }
+
+    #[test]
+    fn test_apply_prestige_bonuses_does_not_overflow() {
+        let mut player_bonuses = create_player_bonuses_for_testing();
+        player_bonuses.critical_chance = u16::MAX - 1;
+
+        apply_prestige_bonuses(&mut player_bonuses, u8::MAX);
+
+        assert_eq!(player_bonuses.critical_chance, u16::MAX);
+    }
+
+    #[test]
+    fn test_apply_skill_bonuses() {
+        let mut player_bonuses = create_player_bonuses_for_testing();
+        let skills = SkillAllocation {
+            fire: 3,
+            water: 0,
+            earth: 1,
+            critical_chance: 2,
+            magic_find: 4,
+            crafting_luck: 1,
+        };
+
+        apply_skill_bonuses(&mut player_bonuses, &skills);
+
+        assert_eq!(player_bonuses.critical_chance, 220);
+        assert_eq!(player_bonuses.magic_find_chance, 40);
+        assert_eq!(player_bonuses.fire_chance, 13);
+        assert_eq!(player_bonuses.water_chance, 0);
+        assert_eq!(player_bonuses.earth_chance, 1);
+        assert_eq!(player_bonuses.crafting_chance, 50);
+    }
+
+    #[test]
+    fn test_apply_set_bonus() {
+        let mut player_bonuses = create_player_bonuses_for_testing();
+
+        apply_set_bonus(&mut player_bonuses, 25);
+
+        assert_eq!(player_bonuses.critical_chance, 250);
+        assert_eq!(player_bonuses.fire_chance, 12);
+        assert_eq!(player_bonuses.water_chance, 0);
+    }
+}
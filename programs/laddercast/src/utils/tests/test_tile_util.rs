@@ -6,7 +6,10 @@ mod test_internal_functions {
     use rand::random;
 
     use crate::{Tile, TileType};
-    use crate::utils::{create_tile_for_testing, cycle_tile, get_current_tile, get_highest_level_and_column};
+    use crate::utils::{
+        create_tile_for_testing, cycle_tile, get_crafting_cost, get_current_tile,
+        get_highest_level_and_column, get_move_cost, is_valid_move, DESCEND_MOVE_COST_MULTIPLIER,
+    };
 
     lazy_static! {
         static ref SLOT_HASHES: [u8; 512 * 40] = generate_slot_hashes(true).try_into().unwrap();
@@ -117,4 +120,56 @@ mod test_internal_functions {
         assert_eq!(current_tile.unwrap().is_first_time_spawning, map[2][1].unwrap().is_first_time_spawning);
         assert_eq!(current_tile.unwrap().life, map[2][1].unwrap().life);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_get_move_cost() {
+        assert_eq!(get_move_cost(0, 0), 10);
+        assert_eq!(get_move_cost(3, 4), 50);
+        //Used to overflow a u8
+        assert_eq!(get_move_cost(28, 29), 300);
+    }
+
+    #[test]
+    fn test_get_move_cost_descending() {
+        assert_eq!(get_move_cost(1, 0), 10 * DESCEND_MOVE_COST_MULTIPLIER);
+        assert_eq!(get_move_cost(5, 4), 50 * DESCEND_MOVE_COST_MULTIPLIER);
+        //Same level is a column move, no extra cost
+        assert_eq!(get_move_cost(4, 4), 50);
+    }
+
+    #[test]
+    fn test_is_valid_move_same_level() {
+        assert!(is_valid_move(2, 0, 2, 1, 5));
+        assert!(is_valid_move(2, 2, 2, 1, 5));
+        assert!(is_valid_move(2, 1, 2, 1, 5));
+        //Can't skip a column
+        assert!(!is_valid_move(2, 0, 2, 2, 5));
+        assert!(!is_valid_move(2, 2, 2, 0, 5));
+    }
+
+    #[test]
+    fn test_is_valid_move_going_up() {
+        assert!(is_valid_move(2, 1, 3, 1, 5));
+        assert!(!is_valid_move(2, 1, 3, 0, 5));
+        assert!(!is_valid_move(2, 1, 3, 2, 5));
+        //Caster level is the limit
+        assert!(is_valid_move(4, 0, 5, 0, 5));
+        assert!(!is_valid_move(5, 0, 6, 0, 5));
+    }
+
+    #[test]
+    fn test_is_valid_move_going_down() {
+        assert!(is_valid_move(3, 1, 2, 1, 5));
+        assert!(is_valid_move(1, 0, 0, 0, 5));
+        assert!(!is_valid_move(3, 1, 2, 0, 5));
+        assert!(!is_valid_move(3, 1, 2, 2, 5));
+        //One level at a time
+        assert!(!is_valid_move(3, 1, 1, 1, 5));
+    }
+
+    #[test]
+    fn test_get_crafting_cost() {
+        assert_eq!(get_crafting_cost(0), 5);
+        assert_eq!(get_crafting_cost(29), 150);
+    }
+}
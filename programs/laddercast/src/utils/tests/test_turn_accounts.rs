@@ -0,0 +1,192 @@
+#[cfg(test)]
+mod test_internal_functions {
+    use anchor_lang::prelude::Pubkey;
+
+    use crate::utils::{
+        ActionType, CommittedActions, ConsumableEffects, ConsumableType, Modifiers, QueuedTurnPlan,
+    };
+
+    #[test]
+    fn test_add_new_action_order() {
+        let mut actions = CommittedActions::default();
+
+        actions.add_new_action_order(ActionType::Spell.get_action_order_index());
+        actions.add_new_action_order(ActionType::Crafting.get_action_order_index());
+        actions.add_new_action_order(ActionType::Loot.get_action_order_index());
+
+        assert_eq!(actions.action_order, [3, 1, 0, 2]);
+    }
+
+    #[test]
+    fn test_remove_action_order() {
+        let mut actions = CommittedActions::default();
+        actions.action_order = [4, 1, 3, 2];
+
+        actions.remove_action_order(ActionType::Crafting.get_action_order_index()).unwrap();
+
+        assert_eq!(actions.action_order, [3, 1, 2, 0]);
+
+        actions.remove_action_order(ActionType::Spell.get_action_order_index()).unwrap();
+
+        assert_eq!(actions.action_order, [2, 0, 1, 0]);
+
+        //New actions go after the remaining ones
+        actions.add_new_action_order(ActionType::Crafting.get_action_order_index());
+
+        assert_eq!(actions.action_order, [2, 0, 1, 3]);
+    }
+
+    #[test]
+    fn test_remove_action_order_not_committed() {
+        let mut actions = CommittedActions::default();
+        actions.action_order = [2, 0, 1, 0];
+
+        actions.remove_action_order(ActionType::Spell.get_action_order_index()).unwrap();
+
+        assert_eq!(actions.action_order, [2, 0, 1, 0]);
+    }
+
+    #[test]
+    fn test_remove_action_order_invalid_index() {
+        let mut actions = CommittedActions::default();
+        actions.action_order = [2, 0, 1, 0];
+
+        assert!(actions.remove_action_order(4).is_err());
+        assert_eq!(actions.action_order, [2, 0, 1, 0]);
+    }
+
+    #[test]
+    fn test_is_committed_after() {
+        let mut actions = CommittedActions::default();
+        actions.action_order = [2, 0, 1, 0];
+
+        assert!(actions.is_committed_after(
+            ActionType::Move.get_action_order_index(),
+            ActionType::Loot.get_action_order_index(),
+        ));
+        assert!(!actions.is_committed_after(
+            ActionType::Loot.get_action_order_index(),
+            ActionType::Move.get_action_order_index(),
+        ));
+        assert!(!actions.is_committed_after(
+            ActionType::Move.get_action_order_index(),
+            ActionType::Crafting.get_action_order_index(),
+        ));
+    }
+
+    #[test]
+    fn test_get_action_tile() {
+        let mut actions = CommittedActions::default();
+
+        assert_eq!(
+            actions.get_action_tile(ActionType::Loot.get_action_order_index(), (3, 1)),
+            (3, 1)
+        );
+
+        actions.mv = Some([2, 1]);
+        actions.add_new_action_order(ActionType::Move.get_action_order_index());
+
+        //Not committed yet, so it will be done after the move
+        assert_eq!(
+            actions.get_action_tile(ActionType::Loot.get_action_order_index(), (3, 1)),
+            (2, 1)
+        );
+
+        actions.add_new_action_order(ActionType::Loot.get_action_order_index());
+
+        assert_eq!(
+            actions.get_action_tile(ActionType::Loot.get_action_order_index(), (3, 1)),
+            (2, 1)
+        );
+
+        //Crafting committed before the move is done on the current tile
+        actions.action_order = [0, 0, 2, 1];
+
+        assert_eq!(
+            actions.get_action_tile(ActionType::Crafting.get_action_order_index(), (3, 1)),
+            (3, 1)
+        );
+    }
+
+    #[test]
+    fn test_is_empty() {
+        let mut actions = CommittedActions::default();
+
+        assert!(actions.is_empty());
+
+        actions.add_new_action_order(ActionType::Loot.get_action_order_index());
+
+        assert!(!actions.is_empty());
+
+        actions.remove_action_order(ActionType::Loot.get_action_order_index()).unwrap();
+
+        assert!(actions.is_empty());
+    }
+
+    #[test]
+    fn test_consumable_effects() {
+        let mut consumables = ConsumableEffects::default();
+
+        assert!(consumables.is_empty());
+
+        consumables.activate(ConsumableType::MoveScroll);
+
+        assert!(!consumables.is_empty());
+        assert!(consumables.free_move);
+        assert!(consumables.is_active(ConsumableType::MoveScroll));
+        assert!(!consumables.is_active(ConsumableType::CritPotion));
+        assert!(!consumables.is_active(ConsumableType::ChestCharm));
+
+        consumables.activate(ConsumableType::ChestCharm);
+
+        assert!(consumables.double_chest_chance);
+        assert!(!consumables.guaranteed_critical);
+    }
+
+    #[test]
+    fn test_modifiers_loadout() {
+        let mut modifiers = Modifiers::default();
+        let head = Pubkey::new_unique();
+        let spell_book = Pubkey::new_unique();
+
+        modifiers.set_loadout([Some(head), None, None, None, None, Some(spell_book)]);
+
+        assert_eq!(modifiers.head, Some(head));
+        assert_eq!(modifiers.spell_book, Some(spell_book));
+        assert_eq!(modifiers.robe, None);
+        assert_eq!(modifiers.get_loadout(), [Some(head), None, None, None, None, Some(spell_book)]);
+
+        modifiers.set_loadout([None; 6]);
+
+        assert!(!modifiers.has_equipped_items());
+    }
+
+    #[test]
+    fn test_queued_turn_plan_is_valid() {
+        assert!(QueuedTurnPlan { loot: true, ..QueuedTurnPlan::default() }.is_valid());
+        assert!(QueuedTurnPlan { mv: Some([29, 2]), ..QueuedTurnPlan::default() }.is_valid());
+        assert!(QueuedTurnPlan {
+            spell: true,
+            crafting: Some([Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()]),
+            ..QueuedTurnPlan::default()
+        }
+        .is_valid());
+    }
+
+    #[test]
+    fn test_queued_turn_plan_is_valid_invalid() {
+        let item = Pubkey::new_unique();
+
+        //Nothing to do
+        assert!(!QueuedTurnPlan::default().is_valid());
+        //Off the map
+        assert!(!QueuedTurnPlan { mv: Some([30, 0]), ..QueuedTurnPlan::default() }.is_valid());
+        assert!(!QueuedTurnPlan { mv: Some([0, 3]), ..QueuedTurnPlan::default() }.is_valid());
+        //Same item twice
+        assert!(!QueuedTurnPlan {
+            crafting: Some([item, Pubkey::new_unique(), item]),
+            ..QueuedTurnPlan::default()
+        }
+        .is_valid());
+    }
+}
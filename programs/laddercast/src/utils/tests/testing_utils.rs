@@ -2,7 +2,7 @@ use anchor_lang::prelude::Pubkey;
 
 use crate::{GameTurnInfo, ItemFeature, ItemType, Tile, TileType};
 use crate::account::{Caster, Game, Item};
-use crate::utils::{EquipmentType, ItemRarity, Modifiers, SpellType};
+use crate::utils::{EquipmentType, ItemRarity, Modifiers, SkillAllocation, SpellType};
 
 //Testing utilities
 pub fn create_caster_for_testing() -> Caster {
@@ -13,6 +13,10 @@ pub fn create_caster_for_testing() -> Caster {
         turn_commit: None,
         modifiers: create_caster_modifiers_for_testing(false),
         owner: Pubkey::new_unique(),
+        prestige: 0,
+        name: [0; 32],
+        skill_points: 0,
+        skills: SkillAllocation::default(),
     }
 }
 
@@ -24,6 +28,8 @@ pub fn create_caster_modifiers_for_testing(with_spell: bool) -> Modifiers {
         robe: Some(Pubkey::new_unique()),
         staff: Some(Pubkey::new_unique()),
         spell_book: None,
+        amulet: Some(Pubkey::new_unique()),
+        ring: Some(Pubkey::new_unique()),
     };
 
     if with_spell {
@@ -80,6 +86,10 @@ pub fn create_equipment_for_testing(equipment_type: EquipmentType) -> Item {
             value: 1,
         },
         equipped_owner: None,
+        reforge_count: 0,
+        wear: 0,
+        secondary_stat: None,
+        spell_book_charges: 0,
     }
 }
 
@@ -96,6 +106,10 @@ pub fn create_spell_book_for_testing() -> Item {
             value: 2,
         },
         equipped_owner: None,
+        reforge_count: 0,
+        wear: 0,
+        secondary_stat: None,
+        spell_book_charges: 0,
     }
 }
 
@@ -108,6 +122,10 @@ pub fn create_chest_for_testing() -> Item {
             tier: 2
         },
         equipped_owner: None,
+        reforge_count: 0,
+        wear: 0,
+        secondary_stat: None,
+        spell_book_charges: 0,
     }
 }
 
@@ -118,5 +136,9 @@ pub fn create_zombie_for_testing() -> Item {
         level: 3,
         item_type: ItemType::Zombie {},
         equipped_owner: None,
+        reforge_count: 0,
+        wear: 0,
+        secondary_stat: None,
+        spell_book_charges: 0,
     }
 }
\ No newline at end of file
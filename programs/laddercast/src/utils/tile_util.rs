@@ -1,5 +1,5 @@
 use crate::{Tile, TileType};
-use crate::utils::{RandomEnumValue, RandomValue};
+use crate::utils::{RandomEnumValue, RandomValue, DESCEND_MOVE_COST_MULTIPLIER};
 
 pub fn cycle_tile(tile: Option<Tile>, level: u8, slots: &[u8], offset: &mut usize) -> Tile {
     match tile {
@@ -62,6 +62,50 @@ pub fn get_highest_level_and_column(map: &[[Option<Tile>; 3]; 30]) -> (u8, u8) {
     (highest_level as u8, highest_column as u8)
 }
 
+pub fn is_valid_move(
+    current_level: u8,
+    current_column: u8,
+    dest_level: u8,
+    dest_column: u8,
+    caster_level: u8,
+) -> bool {
+    //Can only go if your caster is the right level
+    if dest_level > caster_level {
+        return false;
+    }
+
+    let distance = (dest_column as i8 - current_column as i8).abs();
+
+    //Can only move to the column next to you, or change level while staying in the same column
+    if distance > 1 || (dest_level != current_level && distance != 0) {
+        return false;
+    }
+
+    //Going down the ladder is one level at a time
+    if dest_level < current_level && current_level - dest_level > 1 {
+        return false;
+    }
+
+    true
+}
+
+pub fn get_move_cost(current_level: u8, dest_level: u8) -> u64 {
+    //MOVE costs 10*resource of tile you're moving too (since 0 based, gotta add 1)
+    let move_cost = (dest_level as u64 + 1) * 10;
+
+    //Going down costs more so that it's not cheaper than staying on a lower level
+    if dest_level < current_level {
+        move_cost * DESCEND_MOVE_COST_MULTIPLIER
+    } else {
+        move_cost
+    }
+}
+
+pub fn get_crafting_cost(tile_level: u8) -> u64 {
+    //Crafting costs 5*each resource of the caster's tile level (since 0 based, gotta add 1)
+    (tile_level as u64 + 1) * 5
+}
+
 pub fn get_current_tile(map: &[[Option<Tile>; 3]; 30], dest_level: u8, dest_column: u8) -> Option<&Tile> {
     match map.get(dest_level as usize) {
       Some(lvl) =>  match lvl.get(dest_column as usize) {
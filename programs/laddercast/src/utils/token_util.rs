@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+//Resources are always passed in the FIRE_INDEX, WATER_INDEX, EARTH_INDEX order
+pub fn burn_resources<'info>(
+    token_program: AccountInfo<'info>,
+    resource_mint_accounts: [AccountInfo<'info>; 3],
+    resource_token_accounts: [AccountInfo<'info>; 3],
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    resources_to_burn: [u64; 3],
+) -> ProgramResult {
+    for i in 0..resources_to_burn.len() {
+        if resources_to_burn[i] == 0 {
+            continue;
+        }
+
+        token::burn(
+            CpiContext::new(
+                token_program.clone(),
+                token::Burn {
+                    mint: resource_mint_accounts[i].clone(),
+                    to: resource_token_accounts[i].clone(), // STUPID NAME: its the token account you are burning the tokens from
+                    authority: authority.clone(),
+                },
+            )
+                .with_signer(signer_seeds),
+            resources_to_burn[i],
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn mint_resources<'info>(
+    token_program: AccountInfo<'info>,
+    resource_mint_accounts: [AccountInfo<'info>; 3],
+    resource_token_accounts: [AccountInfo<'info>; 3],
+    game_signer: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    resources_to_mint: [u64; 3],
+) -> ProgramResult {
+    for i in 0..resources_to_mint.len() {
+        if resources_to_mint[i] == 0 {
+            continue;
+        }
+
+        token::mint_to(
+            CpiContext::new(
+                token_program.clone(),
+                token::MintTo {
+                    mint: resource_mint_accounts[i].clone(),
+                    to: resource_token_accounts[i].clone(),
+                    authority: game_signer.clone(),
+                },
+            )
+                .with_signer(signer_seeds),
+            resources_to_mint[i],
+        )?;
+    }
+
+    Ok(())
+}
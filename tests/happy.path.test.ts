@@ -33,6 +33,7 @@ describe("laddercast", () => {
   const someGuy = anchor.web3.Keypair.generate();
 
   let someGuyLADATokenAccount: anchor.web3.PublicKey;
+  let inventory: anchor.web3.PublicKey;
 
   //Lada
   const mintAuthority = anchor.web3.Keypair.generate();
@@ -52,7 +53,7 @@ describe("laddercast", () => {
   let caster: anchor.web3.Keypair;
 
   //Items
-  let spellBook: anchor.web3.Keypair;
+  let spellBook: anchor.web3.PublicKey;
 
   //Constants
   const DECIMALS = 1_000_000_000;
@@ -80,6 +81,24 @@ describe("laddercast", () => {
     ];
   }
 
+  //Items are PDAs of the player, derived from the number of items already created for it
+  async function getNextItemAccount(
+    playerAccount: anchor.web3.PublicKey,
+    offset = 0
+  ) {
+    const player = await program.account.player.fetch(playerAccount);
+    const [itemAccount] = await anchor.web3.PublicKey.findProgramAddress(
+      [
+        Buffer.from("item"),
+        playerAccount.toBuffer(),
+        Buffer.from(player.itemCounter.addn(offset).toString()),
+      ],
+      program.programId
+    );
+
+    return itemAccount;
+  }
+
   async function getFeatureForTile(lvl, column) {
     return Object.keys(
       (await program.account.game.fetch(gameAccount.publicKey)).map[lvl][column]
@@ -176,7 +195,12 @@ describe("laddercast", () => {
     });
     const root = tree.getRoot();
 
-    await program.rpc.initGame(gameTurnInfo, [...root], {
+    const [gameConfig] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("game_config"), gameAccount.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.rpc.initGame(gameTurnInfo, [...root], null, {
       accounts: {
         authority: gameAuthority.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -185,6 +209,7 @@ describe("laddercast", () => {
         gameAccount: gameAccount.publicKey,
         gameSigner: gameSigner,
         gameTurnData: gameTurnData,
+        gameConfig: gameConfig,
         slots: "SysvarS1otHashes111111111111111111111111111",
         resource1Mint: mintResource1.publicKey,
         resource2Mint: mintResource2.publicKey,
@@ -237,6 +262,27 @@ describe("laddercast", () => {
     const createdPlayer = await program.account.player.fetch(playerAccount);
 
     assert.deepEqual(createdPlayer.game, gameAccount.publicKey);
+
+    [inventory] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("inventory"), playerAccount.toBuffer()],
+      program.programId
+    );
+
+    await program.rpc.initInventory({
+      accounts: {
+        systemProgram: anchor.web3.SystemProgram.programId,
+        authority: someGuy.publicKey,
+        game: gameAccount.publicKey,
+        player: playerAccount,
+        inventory: inventory,
+      },
+      signers: [someGuy],
+    });
+
+    const createdInventory = await program.account.inventory.fetch(inventory);
+
+    assert.deepEqual(createdInventory.player, playerAccount);
+    assert.equal(createdInventory.items.length, 0);
   });
 
   it("Initializes caster", async () => {
@@ -272,7 +318,7 @@ describe("laddercast", () => {
     const createdCaster = await program.account.caster.fetch(caster.publicKey);
 
     assert.deepEqual(createdCaster.owner, playerAccount);
-    assert.equal(createdCaster.version, 1);
+    assert.equal(createdCaster.version, 2);
     assert.equal(createdCaster.level, 1);
     assert.equal(createdCaster.experience, 0);
     assert.equal(createdCaster.turnCommit, null);
@@ -291,6 +337,8 @@ describe("laddercast", () => {
       robe: null,
       staff: null,
       spellBook: null,
+      amulet: null,
+      ring: null,
     });
 
     assert.equal(
@@ -313,7 +361,7 @@ describe("laddercast", () => {
     );
 
     const chest = { chest: { tier: 1 } };
-    const chestItem = anchor.web3.Keypair.generate();
+    const chestItem = await getNextItemAccount(playerAccount);
 
     await program.rpc.giveItem(chest, new anchor.BN(2), {
       accounts: {
@@ -322,46 +370,103 @@ describe("laddercast", () => {
         authority: someGuy.publicKey,
         player: playerAccount,
         slots: SYSVAR_SLOT_HASHES_PUBKEY,
-        item: chestItem.publicKey,
+        item: chestItem,
+        inventory: inventory,
       },
-      signers: [someGuy, chestItem],
+      signers: [someGuy],
     });
 
     const newItems = [
-      anchor.web3.Keypair.generate(),
-      anchor.web3.Keypair.generate(),
-      anchor.web3.Keypair.generate(),
+      await getNextItemAccount(playerAccount, 0),
+      await getNextItemAccount(playerAccount, 1),
+      await getNextItemAccount(playerAccount, 2),
     ];
 
+    const [gameConfig] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("game_config"), gameAccount.publicKey.toBuffer()],
+      program.programId
+    );
+    const [gameSigner] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("game_signer")],
+      program.programId
+    );
+    const [gameTurnData] = await anchor.web3.PublicKey.findProgramAddress(
+      [
+        Buffer.from("turn_data"),
+        gameAccount.publicKey.toBuffer(),
+        Buffer.from(anchor.utils.bytes.utf8.encode(String(gameTurnInfo.turn))),
+      ],
+      program.programId
+    );
+
+    //Tier 1 chests give 3 items, each item drop uses one of the new accounts, each item drop uses one of the new accounts
     await program.rpc.openChest({
       accounts: {
+        systemProgram: anchor.web3.SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
         game: gameAccount.publicKey,
+        gameConfig: gameConfig,
         authority: someGuy.publicKey,
         player: playerAccount,
+        gameSigner: gameSigner,
         slots: "SysvarS1otHashes111111111111111111111111111",
-        chest: chestItem.publicKey,
-        item1: newItems[0].publicKey,
-        item2: newItems[1].publicKey,
-        item3: newItems[2].publicKey,
-        systemProgram: anchor.web3.SystemProgram.programId,
+        resource1MintAccount: mintResource1.publicKey,
+        resource2MintAccount: mintResource2.publicKey,
+        resource3MintAccount: mintResource3.publicKey,
+        resource1TokenAccount: ATAResource1,
+        resource2TokenAccount: ATAResource2,
+        resource3TokenAccount: ATAResource3,
+        gameLadaTokenAccount: gameLADATokenAccount,
+        ladaTokenAccount: someGuyLADATokenAccount,
+        gameTurnData: gameTurnData,
+        chest: chestItem,
+        inventory: inventory,
       },
-      signers: [someGuy, newItems[0], newItems[1], newItems[2]],
+      remainingAccounts: newItems.map((newItem) => ({
+        pubkey: newItem,
+        isSigner: false,
+        isWritable: true,
+      })),
+      signers: [someGuy],
     });
 
     const openedChest = await program.account.item.fetchNullable(
-      chestItem.publicKey
+      chestItem
     );
 
     //Is null because we close the account
     assert.equal(openedChest, null);
 
+    const fetchedPlayer = await program.account.player.fetch(playerAccount);
+
+    assert.equal(fetchedPlayer.chestsOpened.toNumber(), 1);
+
+    //The inventory follows the items created and burned
+    const fetchedInventory = await program.account.inventory.fetch(inventory);
+
+    assert.equal(
+      fetchedInventory.items.some((key) => key.equals(chestItem)),
+      false
+    );
+
+    //The default loot tables only drop items
     for (let i = 0; i < newItems.length; i++) {
-      let newItem = await program.account.item.fetch(newItems[i].publicKey);
+      let newItem = await program.account.item.fetchNullable(
+        newItems[i]
+      );
+
+      assert.notEqual(newItem, null);
 
       assert.equal(newItem.level <= 2 && newItem.level >= 1, true);
       assert.deepEqual(newItem.game, gameAccount.publicKey);
       assert.deepEqual(newItem.owner, playerAccount);
       assert.equal(newItem.equippedOwner, null);
+      assert.equal(
+        fetchedInventory.items.some((key) => key.equals(newItems[i])),
+        true
+      );
     }
   });
 
@@ -380,7 +485,7 @@ describe("laddercast", () => {
       },
     };
 
-    const equipmentItem = anchor.web3.Keypair.generate();
+    const equipmentItem = await getNextItemAccount(playerAccount);
 
     await program.rpc.giveItem(equipment, new anchor.BN(1), {
       accounts: {
@@ -389,9 +494,10 @@ describe("laddercast", () => {
         authority: someGuy.publicKey,
         player: playerAccount,
         slots: SYSVAR_SLOT_HASHES_PUBKEY,
-        item: equipmentItem.publicKey,
+        item: equipmentItem,
+        inventory: inventory,
       },
-      signers: [someGuy, equipmentItem],
+      signers: [someGuy],
     });
 
     await program.rpc.equipItem({
@@ -400,13 +506,13 @@ describe("laddercast", () => {
         authority: someGuy.publicKey,
         player: playerAccount,
         caster: caster.publicKey,
-        item: equipmentItem.publicKey,
+        item: equipmentItem,
       },
       signers: [someGuy],
     });
 
     let equippedItem = await program.account.item.fetch(
-      equipmentItem.publicKey
+      equipmentItem
     );
     assert.deepEqual(equippedItem.equippedOwner, caster.publicKey);
 
@@ -416,12 +522,12 @@ describe("laddercast", () => {
         authority: someGuy.publicKey,
         player: playerAccount,
         caster: caster.publicKey,
-        item: equipmentItem.publicKey,
+        item: equipmentItem,
       },
       signers: [someGuy],
     });
 
-    equippedItem = await program.account.item.fetch(equipmentItem.publicKey);
+    equippedItem = await program.account.item.fetch(equipmentItem);
     assert.deepEqual(equippedItem.equippedOwner, null);
 
     const spellbook = {
@@ -433,7 +539,7 @@ describe("laddercast", () => {
         value: 1,
       },
     };
-    const spellbookItem = anchor.web3.Keypair.generate();
+    const spellbookItem = await getNextItemAccount(playerAccount);
 
     await program.rpc.giveItem(spellbook, new anchor.BN(1), {
       accounts: {
@@ -442,9 +548,10 @@ describe("laddercast", () => {
         authority: someGuy.publicKey,
         player: playerAccount,
         slots: SYSVAR_SLOT_HASHES_PUBKEY,
-        item: spellbookItem.publicKey,
+        item: spellbookItem,
+        inventory: inventory,
       },
-      signers: [someGuy, spellbookItem],
+      signers: [someGuy],
     });
 
     await program.rpc.equipItem({
@@ -453,13 +560,13 @@ describe("laddercast", () => {
         authority: someGuy.publicKey,
         player: playerAccount,
         caster: caster.publicKey,
-        item: spellbookItem.publicKey,
+        item: spellbookItem,
       },
       signers: [someGuy],
     });
 
     let equippedSpellBook = await program.account.item.fetch(
-      spellbookItem.publicKey
+      spellbookItem
     );
     assert.deepEqual(equippedSpellBook.equippedOwner, caster.publicKey);
 
@@ -469,13 +576,13 @@ describe("laddercast", () => {
         authority: someGuy.publicKey,
         player: playerAccount,
         caster: caster.publicKey,
-        item: spellbookItem.publicKey,
+        item: spellbookItem,
       },
       signers: [someGuy],
     });
 
     equippedSpellBook = await program.account.item.fetch(
-      spellbookItem.publicKey
+      spellbookItem
     );
     assert.deepEqual(equippedSpellBook.equippedOwner, null);
   });
@@ -528,7 +635,7 @@ describe("laddercast", () => {
         value: 1,
       },
     };
-    spellBook = anchor.web3.Keypair.generate();
+    spellBook = await getNextItemAccount(playerAccount);
 
     await program.rpc.giveItem(spellbook, new anchor.BN(1), {
       accounts: {
@@ -537,9 +644,10 @@ describe("laddercast", () => {
         authority: someGuy.publicKey,
         player: playerAccount,
         slots: SYSVAR_SLOT_HASHES_PUBKEY,
-        item: spellBook.publicKey,
+        item: spellBook,
+        inventory: inventory,
       },
-      signers: [someGuy, spellBook],
+      signers: [someGuy],
     });
 
     await program.rpc.equipItem({
@@ -548,7 +656,7 @@ describe("laddercast", () => {
         authority: someGuy.publicKey,
         player: playerAccount,
         caster: caster.publicKey,
-        item: spellBook.publicKey,
+        item: spellBook,
       },
       signers: [someGuy],
     });
@@ -559,6 +667,11 @@ describe("laddercast", () => {
 
     const someGuyFireResource = await getTokenAccountBalance(ATAResource1);
 
+    const [casterStats] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("caster_stats"), caster.publicKey.toBuffer()],
+      program.programId
+    );
+
     await program.rpc.casterCommitSpell({
       accounts: {
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -569,6 +682,7 @@ describe("laddercast", () => {
         game: gameAccount.publicKey,
         player: playerAccount,
         caster: caster.publicKey,
+        casterStats: casterStats,
         slots: SYSVAR_SLOT_HASHES_PUBKEY,
         resource1MintAccount: mintResource1.publicKey,
         resource2MintAccount: mintResource2.publicKey,
@@ -576,7 +690,7 @@ describe("laddercast", () => {
         resource1TokenAccount: ATAResource1,
         resource2TokenAccount: ATAResource2,
         resource3TokenAccount: ATAResource3,
-        spellbook: spellBook.publicKey,
+        spellbook: spellBook,
         gameTurnData: gameTurnData,
       },
       signers: [someGuy],
@@ -615,6 +729,11 @@ describe("laddercast", () => {
         mv: null,
         crafting: null,
         actionOrder: [0, 1, 0, 0],
+        consumables: {
+          guaranteedCritical: false,
+          freeMove: false,
+          doubleChestChance: false,
+        },
       },
     });
   });
@@ -641,7 +760,7 @@ describe("laddercast", () => {
         value: 1,
       },
     };
-    const item1 = anchor.web3.Keypair.generate();
+    const item1 = await getNextItemAccount(playerAccount);
 
     await program.rpc.giveItem(equipment, new anchor.BN(1), {
       accounts: {
@@ -650,12 +769,13 @@ describe("laddercast", () => {
         authority: someGuy.publicKey,
         player: playerAccount,
         slots: SYSVAR_SLOT_HASHES_PUBKEY,
-        item: item1.publicKey,
+        item: item1,
+        inventory: inventory,
       },
-      signers: [someGuy, item1],
+      signers: [someGuy],
     });
 
-    const item2 = anchor.web3.Keypair.generate();
+    const item2 = await getNextItemAccount(playerAccount);
 
     await program.rpc.giveItem(equipment, new anchor.BN(1), {
       accounts: {
@@ -664,12 +784,13 @@ describe("laddercast", () => {
         authority: someGuy.publicKey,
         player: playerAccount,
         slots: SYSVAR_SLOT_HASHES_PUBKEY,
-        item: item2.publicKey,
+        item: item2,
+        inventory: inventory,
       },
-      signers: [someGuy, item2],
+      signers: [someGuy],
     });
 
-    const item3 = anchor.web3.Keypair.generate();
+    const item3 = await getNextItemAccount(playerAccount);
 
     await program.rpc.giveItem(equipment, new anchor.BN(1), {
       accounts: {
@@ -678,9 +799,10 @@ describe("laddercast", () => {
         authority: someGuy.publicKey,
         player: playerAccount,
         slots: SYSVAR_SLOT_HASHES_PUBKEY,
-        item: item3.publicKey,
+        item: item3,
+        inventory: inventory,
       },
-      signers: [someGuy, item3],
+      signers: [someGuy],
     });
 
     const currentModifiers = (await program.account.caster.all())[0].account
@@ -707,6 +829,11 @@ describe("laddercast", () => {
     ).turnCommit.resourcesBurned;
     let preSomeGuyResources = await getTokenAccountBalances();
 
+    const [casterStats] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("caster_stats"), caster.publicKey.toBuffer()],
+      program.programId
+    );
+
     await program.rpc.casterCommitCraft({
       accounts: {
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -717,6 +844,7 @@ describe("laddercast", () => {
         game: gameAccount.publicKey,
         player: playerAccount,
         caster: caster.publicKey,
+        casterStats: casterStats,
         slots: SYSVAR_SLOT_HASHES_PUBKEY,
         resource1MintAccount: mintResource1.publicKey,
         resource2MintAccount: mintResource2.publicKey,
@@ -724,10 +852,11 @@ describe("laddercast", () => {
         resource1TokenAccount: ATAResource1,
         resource2TokenAccount: ATAResource2,
         resource3TokenAccount: ATAResource3,
-        item1: item1.publicKey,
-        item2: item2.publicKey,
-        item3: item3.publicKey,
+        item1: item1,
+        item2: item2,
+        item3: item3,
         gameTurnData: gameTurnData,
+        inventory: inventory,
       },
       signers: [someGuy],
     });
@@ -780,15 +909,15 @@ describe("laddercast", () => {
 
     //Assert items are deleted
     assert.equal(
-      await program.account.item.fetchNullable(item1.publicKey),
+      await program.account.item.fetchNullable(item1),
       null
     );
     assert.equal(
-      await program.account.item.fetchNullable(item2.publicKey),
+      await program.account.item.fetchNullable(item2),
       null
     );
     assert.equal(
-      await program.account.item.fetchNullable(item3.publicKey),
+      await program.account.item.fetchNullable(item3),
       null
     );
 
@@ -824,6 +953,11 @@ describe("laddercast", () => {
           maxRarity: { epic: {} },
         },
         actionOrder: [0, 1, 0, 2],
+        consumables: {
+          guaranteedCritical: false,
+          freeMove: false,
+          doubleChestChance: false,
+        },
       },
     });
   });
@@ -850,6 +984,11 @@ describe("laddercast", () => {
     ).turnCommit.resourcesBurned;
     let preSomeGuyResources = await getTokenAccountBalances();
 
+    const [casterStats] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("caster_stats"), caster.publicKey.toBuffer()],
+      program.programId
+    );
+
     await program.rpc.casterCommitMove(0, 1, {
       accounts: {
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -860,6 +999,7 @@ describe("laddercast", () => {
         game: gameAccount.publicKey,
         player: playerAccount,
         caster: caster.publicKey,
+        casterStats: casterStats,
         slots: SYSVAR_SLOT_HASHES_PUBKEY,
         resource1MintAccount: mintResource1.publicKey,
         resource2MintAccount: mintResource2.publicKey,
@@ -936,6 +1076,11 @@ describe("laddercast", () => {
           maxRarity: { epic: {} },
         },
         actionOrder: [0, 1, 3, 2],
+        consumables: {
+          guaranteedCritical: false,
+          freeMove: false,
+          doubleChestChance: false,
+        },
       },
     });
   });
@@ -962,6 +1107,11 @@ describe("laddercast", () => {
       },
     });
 
+    const [casterStats] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("caster_stats"), caster.publicKey.toBuffer()],
+      program.programId
+    );
+
     await program.rpc.casterCommitLoot({
       accounts: {
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -969,6 +1119,7 @@ describe("laddercast", () => {
         game: gameAccount.publicKey,
         player: playerAccount,
         caster: caster.publicKey,
+        casterStats: casterStats,
         gameTurnData: gameTurnData,
       },
       signers: [someGuy],
@@ -993,8 +1144,21 @@ describe("laddercast", () => {
           maxRarity: { epic: {} },
         },
         actionOrder: [4, 1, 3, 2],
+        consumables: {
+          guaranteedCritical: false,
+          freeMove: false,
+          doubleChestChance: false,
+        },
       },
     });
+
+    //All the actions were committed during the same turn
+    const fetchedCasterStats = await program.account.casterStats.fetch(
+      casterStats
+    );
+
+    assert.equal(fetchedCasterStats.turnsPlayed.toNumber(), 1);
+    assert.equal(fetchedCasterStats.spellsCast.toNumber(), 1);
   });
 
   it("can manual_resource_burn", async () => {
@@ -1020,6 +1184,11 @@ describe("laddercast", () => {
     ).turnCommit.resourcesBurned;
     let preSomeGuyResources = await getTokenAccountBalances();
 
+    const [gameConfig] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("game_config"), gameAccount.publicKey.toBuffer()],
+      program.programId
+    );
+
     await program.rpc.manualResourceBurn({ water: {} }, new anchor.BN(10), {
       accounts: {
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -1028,6 +1197,7 @@ describe("laddercast", () => {
         rent: SYSVAR_RENT_PUBKEY,
         authority: someGuy.publicKey,
         game: gameAccount.publicKey,
+        gameConfig: gameConfig,
         player: playerAccount,
         caster: caster.publicKey,
         resource1MintAccount: mintResource1.publicKey,
@@ -1091,6 +1261,11 @@ describe("laddercast", () => {
       program.programId
     );
 
+    const [gameConfig] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("game_config"), gameAccount.publicKey.toBuffer()],
+      program.programId
+    );
+
     let oldFetchedGame = await program.account.game.fetch(
       gameAccount.publicKey
     );
@@ -1101,6 +1276,8 @@ describe("laddercast", () => {
         systemProgram: anchor.web3.SystemProgram.programId,
         rent: SYSVAR_RENT_PUBKEY,
         gameAccount: gameAccount.publicKey,
+        gameConfig: gameConfig,
+        gameLadaTokenAccount: gameLADATokenAccount,
         slots: SYSVAR_SLOT_HASHES_PUBKEY,
         gameTurnData: gameTurnData,
         currentGameTurnData: currentGameTurnData,
@@ -1108,6 +1285,17 @@ describe("laddercast", () => {
       signers: [someGuy],
     });
 
+    //The new turn gets the flat default emission, as long as the game has the LADA
+    const fetchedTurnData = await program.account.turnData.fetch(gameTurnData);
+
+    assert.equal(
+      fetchedTurnData.ladaToDistribute.toNumber(),
+      Math.min(
+        1_984_126_984_130,
+        await getTokenAccountBalance(gameLADATokenAccount)
+      )
+    );
+
     let fetchedGame = await program.account.game.fetch(gameAccount.publicKey);
 
     assert.equal(fetchedGame.turnInfo.turn, 2);
@@ -1146,14 +1334,14 @@ describe("laddercast", () => {
       program.programId
     );
 
-    const item = anchor.web3.Keypair.generate();
+    const item = await getNextItemAccount(playerAccount);
     const empty = anchor.web3.Keypair.generate();
 
     const oldFetchedCaster = await program.account.caster.fetch(
       caster.publicKey
     );
     const itemPreCreation = await program.account.item.fetchNullable(
-      item.publicKey
+      item
     );
     const playerLadaBalanceBefore = await getTokenAccountBalance(
       someGuyLADATokenAccount
@@ -1163,6 +1351,16 @@ describe("laddercast", () => {
     );
     const preSomeGuyResources = await getTokenAccountBalances();
 
+    const [casterStats] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("caster_stats"), caster.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const [gameConfig] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("game_config"), gameAccount.publicKey.toBuffer()],
+      program.programId
+    );
+
     await program.rpc.casterRedeemAction({
       accounts: {
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -1171,8 +1369,10 @@ describe("laddercast", () => {
         rent: SYSVAR_RENT_PUBKEY,
         authority: someGuy.publicKey,
         game: gameAccount.publicKey,
+        gameConfig: gameConfig,
         player: playerAccount,
         caster: caster.publicKey,
+        casterStats: casterStats,
         gameSigner: gameSigner,
         slots: SYSVAR_SLOT_HASHES_PUBKEY,
         resource1MintAccount: mintResource1.publicKey,
@@ -1185,15 +1385,18 @@ describe("laddercast", () => {
         gameLadaTokenAccount: gameLADATokenAccount,
         ladaTokenAccount: someGuyLADATokenAccount,
         gameTurnData: gameTurnData,
-        item: item.publicKey,
+        item: item,
         staff: empty.publicKey,
         head: empty.publicKey,
         robe: empty.publicKey,
+        amulet: empty.publicKey,
+        ring: empty.publicKey,
+        inventory: inventory,
       },
-      signers: [item, someGuy],
+      signers: [someGuy],
       remainingAccounts: [
         {
-          pubkey: spellBook.publicKey,
+          pubkey: spellBook,
           isSigner: false,
           isWritable: true,
         },
@@ -1213,13 +1416,13 @@ describe("laddercast", () => {
     //Assert craft
     assert.equal(itemPreCreation, null);
     assert.equal(
-      (await program.account.item.fetchNullable(item.publicKey)) !== null,
+      (await program.account.item.fetchNullable(item)) !== null,
       true
     );
 
-    //Assert spell (get zombified)
+    //Assert spell (get zombified after its last charge)
     assert.equal(
-      await program.account.item.fetchNullable(spellBook.publicKey),
+      await program.account.item.fetchNullable(spellBook),
       null
     );
 